@@ -0,0 +1,12 @@
+{
+  "spriteId": null,
+  "solid": true,
+  "visible": true,
+  "eventList": [],
+  "parent": {
+    "name": "Objects",
+    "path": "folders/Objects.yy",
+  },
+  "name": "obj_wall",
+  "resourceType": "GMObject",
+}
@@ -0,0 +1,9 @@
+{
+  "isDnD": false,
+  "parent": {
+    "name": "Scripts",
+    "path": "folders/Scripts.yy",
+  },
+  "name": "scr_move",
+  "resourceType": "GMScript",
+}
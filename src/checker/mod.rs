@@ -0,0 +1,592 @@
+//! Checks the types of a GML++ program. Types are optional, so anything whose type is not known is
+//! assumed to be fine
+
+use std::collections::HashMap;
+
+use gmlpp::AST;
+use gmlpp::ast::{ArgumentList, Assignment, Call, Code, Expression, Literal, LValue, Accessor, Match, Pattern, Statement, StatementKind, Type, Value};
+use gmlpp::Span;
+use symbols::Symbols;
+use error::{Error, TypeError, Warning};
+
+/// The types of the variables declared in a single block. Variables without a type are still
+/// recorded, as they hide any typed variable of the same name
+type Scope = HashMap<String, Option<Type>>;
+
+struct Checker<'a> {
+    symbols: &'a Symbols,
+    scopes: Vec<Scope>,
+    returns: Option<Type>,
+    warnings: Vec<Warning>,
+    errors: Vec<Error>,
+}
+
+/// Checks that every value in a program is used where its type is allowed, and that every function
+/// called exists and is given the right number of arguments. Checking carries on past each error, so
+/// all of them are reported at once
+pub fn check(ast: &AST, symbols: &Symbols) -> Result<Vec<Warning>, Vec<Error>> {
+    let mut checker = Checker { symbols, scopes: vec![Scope::new()], returns: None, warnings: vec![], errors: vec![] };
+    checker.code(ast.code());
+    if checker.errors.is_empty() {
+        Ok(checker.warnings)
+    } else {
+        Err(checker.errors)
+    }
+}
+
+/// Makes sure a value of the found type can be used where the expected type is required
+fn expect(expected: Option<Type>, found: Option<Type>) -> Result<(), Error> {
+    match (expected, found) {
+        (Some(expected), Some(found)) if !expected.accepts(found) => Err(Error::TypeError(TypeError::Mismatch { expected, found })),
+        _ => Ok(()),
+    }
+}
+
+/// The type of the elements of a collection which are reached through an accessor
+fn accessed(accessor: &Accessor) -> Type {
+    match accessor {
+        Accessor::Array | Accessor::ArrayRef => Type::Array,
+        Accessor::Map => Type::Map,
+        Accessor::List => Type::List,
+        Accessor::Grid => Type::Grid,
+    }
+}
+
+impl<'a> Checker<'a> {
+    fn scoped<F>(&mut self, check: F) -> Result<(), Error>
+    where F: FnOnce(&mut Self) -> Result<(), Error> {
+        self.scopes.push(Scope::new());
+        let result = check(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn declare(&mut self, name: &str, ty: Option<Type>) {
+        self.scopes.last_mut().unwrap().insert(name.to_owned(), ty);
+    }
+
+    fn lookup(&self, name: &str) -> Option<Type> {
+        self.scopes.iter().rev().filter_map(|scope| scope.get(name)).next().and_then(|ty| *ty)
+    }
+
+    fn code(&mut self, code: &Code) {
+        self.returns = code.returns;
+        let mut args = &code.args;
+        loop {
+            args = match *args {
+                ArgumentList::Argument(ref ident, ty, ref rest) | ArgumentList::OptionalArgument(ref ident, ty, ref rest) => {
+                    self.declare(&ident.0, ty);
+                    rest
+                }
+                ArgumentList::DefaultArgument(ref ident, ty, ref default, ref rest) => {
+                    if let Err(error) = self.expected(ty, default) {
+                        self.errors.push(error);
+                    }
+                    self.declare(&ident.0, ty);
+                    rest
+                }
+                ArgumentList::VariadicArgument(ref ident, _) => {
+                    // the annotation is the type of each value, which are collected into an array
+                    self.declare(&ident.0, Some(Type::Array));
+                    break;
+                }
+                ArgumentList::End => break,
+            };
+        }
+        for statement in &code.body.0 {
+            self.statement(statement);
+        }
+    }
+
+    /// Checks a statement, recording any error which stops it being checked and carrying on with the
+    /// next one. Errors which are not located more precisely are located at the statement
+    fn statement(&mut self, statement: &Statement) {
+        if let Err(error) = self.statement_kind(&statement.kind) {
+            self.errors.push(error.at(statement.span));
+        }
+    }
+
+    fn statement_kind(&mut self, statement: &StatementKind) -> Result<(), Error> {
+        match statement {
+            StatementKind::Assignment(ref assignment) => self.assignment(assignment),
+            StatementKind::Expression(ref expr) => self.expression(expr).map(|_| ()),
+            StatementKind::VarDecl(ref ident, ty) | StatementKind::LetDecl(ref ident, ty) => {
+                self.declare(&ident.0, *ty);
+                Ok(())
+            }
+            StatementKind::VarDeclAssign(ref ident, ty, ref expr) | StatementKind::LetDeclAssign(ref ident, ty, ref expr) => {
+                self.expected(*ty, expr)?;
+                self.declare(&ident.0, *ty);
+                Ok(())
+            }
+            StatementKind::ConstDecl(ref ident, ty, ref expr) => {
+                // a constant never changes, so it always has the type of its value
+                let found = self.expected(*ty, expr)?;
+                self.declare(&ident.0, ty.or(found));
+                Ok(())
+            }
+            StatementKind::GlobalvarDecl(..) => Ok(()),
+            StatementKind::GlobalvarDeclAssign(_, ref expr) => self.expression(expr).map(|_| ()),
+            StatementKind::EnumDecl(_, ref members) => {
+                for member in members {
+                    if let Some(ref value) = member.value {
+                        self.expected(Some(Type::Number), value)?;
+                    }
+                }
+                Ok(())
+            }
+            StatementKind::If(ref cond, ref tbody, ref fbody) => {
+                self.condition(cond)?;
+                self.statement(tbody);
+                if let Some(ref fbody) = fbody {
+                    self.statement(fbody);
+                }
+                Ok(())
+            }
+            StatementKind::While(ref cond, ref body) | StatementKind::Until(ref cond, ref body)
+            | StatementKind::DoWhile(ref body, ref cond) | StatementKind::DoUntil(ref body, ref cond) => {
+                self.condition(cond)?;
+                self.statement(body);
+                Ok(())
+            }
+            StatementKind::Repeat(ref times, ref body) => {
+                self.expected(Some(Type::Number), times)?;
+                self.statement(body);
+                Ok(())
+            }
+            StatementKind::For(ref init, ref cond, ref update, ref body) => self.scoped(|checker| {
+                checker.statement(init);
+                checker.condition(cond)?;
+                checker.statement(update);
+                checker.statement(body);
+                Ok(())
+            }),
+            StatementKind::ForIn(ref ident, ref expr, ref body) => {
+                let key = match self.expression(expr)? {
+                    Some(Type::Array) | Some(Type::List) => Some(Type::Number),
+                    _ => None,
+                };
+                self.scoped(|checker| {
+                    checker.declare(&ident.0, key);
+                    checker.statement(body);
+                    Ok(())
+                })
+            }
+            StatementKind::ForRange(ref ident, ref start, ref end, ref body) => {
+                self.expected(Some(Type::Number), start)?;
+                self.expected(Some(Type::Number), end)?;
+                self.scoped(|checker| {
+                    checker.declare(&ident.0, Some(Type::Number));
+                    checker.statement(body);
+                    Ok(())
+                })
+            }
+            StatementKind::ForOf(ref ident, ref expr, ref body) => {
+                match self.expression(expr)? {
+                    Some(Type::Array) | Some(Type::List) | Some(Type::Map) | None => {}
+                    found => expect(Some(Type::Array), found)?,
+                }
+                self.scoped(|checker| {
+                    checker.declare(&ident.0, None);
+                    checker.statement(body);
+                    Ok(())
+                })
+            }
+            StatementKind::With(ref target, ref body) => {
+                self.expression(target)?;
+                self.statement(body);
+                Ok(())
+            }
+            StatementKind::Loop(ref body) => {
+                self.statement(body);
+                Ok(())
+            }
+            StatementKind::Block(ref statements) => self.scoped(|checker| {
+                for statement in statements {
+                    checker.statement(statement);
+                }
+                Ok(())
+            }),
+            StatementKind::Switch(ref value, ref cases) => {
+                self.expression(value)?;
+                self.scoped(|checker| {
+                    for case in cases {
+                        if let Some(ref label) = case.label {
+                            if let Err(error) = checker.expression(label) {
+                                checker.errors.push(error);
+                            }
+                        }
+                        for statement in &case.body {
+                            checker.statement(statement);
+                        }
+                    }
+                    Ok(())
+                })
+            }
+            StatementKind::Match(ref expr) => self.matching(expr).map(|_| ()),
+            StatementKind::Return(None) => match self.returns {
+                Some(Type::Void) | None => Ok(()),
+                Some(expected) => Err(Error::TypeError(TypeError::Mismatch { expected, found: Type::Void })),
+            },
+            StatementKind::Return(Some(ref expr)) => {
+                self.expected(self.returns, expr).map(|_| ())
+            }
+            StatementKind::Break | StatementKind::Exit | StatementKind::Continue | StatementKind::Noop => Ok(()),
+        }
+    }
+
+    fn condition(&mut self, cond: &Expression) -> Result<(), Error> {
+        self.expected(Some(Type::Bool), cond).map(|_| ())
+    }
+
+    fn assignment(&mut self, assignment: &Assignment) -> Result<(), Error> {
+        use self::Assignment::*;
+        match assignment {
+            Eq(ref lvalue, ref expr) => {
+                let expected = self.lvalue(lvalue)?;
+                self.expected(expected, expr).map(|_| ())
+            }
+            Plus(ref lvalue, ref expr) => {
+                let current = self.lvalue(lvalue)?;
+                let found = self.expression(expr)?;
+                self.plus(current, found)
+                    .and_then(|result| expect(current, result))
+                    .map_err(|error| error.at(expr.span()))
+            }
+            Minus(ref lvalue, ref expr) | Times(ref lvalue, ref expr) | Slash(ref lvalue, ref expr)
+            | Pct(ref lvalue, ref expr) | Exp(ref lvalue, ref expr) | And(ref lvalue, ref expr)
+            | Or(ref lvalue, ref expr) | Xor(ref lvalue, ref expr) | LShift(ref lvalue, ref expr)
+            | RShift(ref lvalue, ref expr) => {
+                let current = self.lvalue(lvalue)?;
+                expect(Some(Type::Number), current).map_err(|error| error.at(lvalue.span()))?;
+                self.expected(Some(Type::Number), expr).map(|_| ())
+            }
+        }
+    }
+
+    /// The type of adding two values, which joins them if either is a string
+    fn plus(&self, lhs: Option<Type>, rhs: Option<Type>) -> Result<Option<Type>, Error> {
+        match (lhs, rhs) {
+            (Some(Type::String), other) | (Some(Type::Char), other) | (other, Some(Type::String)) | (other, Some(Type::Char)) => {
+                expect(Some(Type::String), other)?;
+                Ok(Some(Type::String))
+            }
+            (None, None) => Ok(None),
+            _ => {
+                expect(Some(Type::Number), lhs)?;
+                expect(Some(Type::Number), rhs)?;
+                Ok(Some(Type::Number))
+            }
+        }
+    }
+
+    /// The type of an expression, if it can be known
+    fn expression(&mut self, expr: &Expression) -> Result<Option<Type>, Error> {
+        self.infer(expr).map_err(|error| error.at(expr.span()))
+    }
+
+    /// The type of an expression, making sure it is the expected type. As the type is known either
+    /// way, a mismatch is recorded and checking carries on
+    fn expected(&mut self, expected: Option<Type>, expr: &Expression) -> Result<Option<Type>, Error> {
+        let found = self.expression(expr)?;
+        if let Err(error) = expect(expected, found) {
+            self.errors.push(error.at(expr.span()));
+        }
+        Ok(found)
+    }
+
+    fn infer(&mut self, expr: &Expression) -> Result<Option<Type>, Error> {
+        use self::Expression::*;
+        match expr {
+            Plus(ref lhs, ref rhs) => {
+                let lhs = self.expression(lhs)?;
+                let rhs = self.expression(rhs)?;
+                self.plus(lhs, rhs)
+            }
+            Minus(ref lhs, ref rhs) | Times(ref lhs, ref rhs) | Slash(ref lhs, ref rhs)
+            | Pct(ref lhs, ref rhs) | Exp(ref lhs, ref rhs) | Mod(ref lhs, ref rhs)
+            | Div(ref lhs, ref rhs) | LShift(ref lhs, ref rhs) | RShift(ref lhs, ref rhs)
+            | And(ref lhs, ref rhs) | Or(ref lhs, ref rhs) | Xor(ref lhs, ref rhs) => {
+                self.expected(Some(Type::Number), lhs)?;
+                self.expected(Some(Type::Number), rhs)?;
+                Ok(Some(Type::Number))
+            }
+            BAnd(ref lhs, ref rhs) | BOr(ref lhs, ref rhs) => {
+                self.condition(lhs)?;
+                self.condition(rhs)?;
+                Ok(Some(Type::Bool))
+            }
+            Eq(ref lhs, ref rhs) | Neq(ref lhs, ref rhs) => {
+                // values of unrelated types can never be equal, so comparing them is a mistake
+                let lhs = self.expression(lhs)?;
+                let rhs = self.expression(rhs)?;
+                if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+                    if !lhs.accepts(rhs) && !rhs.accepts(lhs) {
+                        return Err(Error::TypeError(TypeError::Mismatch { expected: lhs, found: rhs }));
+                    }
+                }
+                Ok(Some(Type::Bool))
+            }
+            Lt(ref lhs, ref rhs) | Gt(ref lhs, ref rhs) | Leq(ref lhs, ref rhs) | Geq(ref lhs, ref rhs) => {
+                self.expression(lhs)?;
+                self.expression(rhs)?;
+                Ok(Some(Type::Bool))
+            }
+            Pipe(ref lhs, ref call) => {
+                let piped = self.expression(lhs)?;
+                self.call(call, Some((piped, lhs.span())))
+            }
+            Ternary(ref cond, ref first, ref second) => {
+                self.condition(cond)?;
+                let first = self.expression(first)?;
+                let second = self.expression(second)?;
+                Ok(if first == second { first } else { None })
+            }
+            Value(ref value, _) => self.value(value),
+        }
+    }
+
+    fn value(&mut self, value: &Value) -> Result<Option<Type>, Error> {
+        match value {
+            Value::LValue(ref lvalue) => self.lvalue(lvalue),
+            Value::Literal(ref literal) => Ok(match literal {
+                Literal::Numeric(..) => Some(Type::Number),
+                Literal::Boolean(..) => Some(Type::Bool),
+                Literal::String(..) => Some(Type::String),
+                Literal::Char(..) => Some(Type::Char),
+                Literal::Undefined => None,
+            }),
+            Value::Expr(ref expr) => self.expression(expr),
+            Value::Call(ref call) => self.call(call, None),
+            Value::Match(ref expr) => self.matching(expr),
+            Value::Negative(ref inner) | Value::Inverted(ref inner) => {
+                let found = self.value(inner)?;
+                expect(Some(Type::Number), found)?;
+                Ok(Some(Type::Number))
+            }
+            Value::Not(ref inner) => {
+                let found = self.value(inner)?;
+                expect(Some(Type::Bool), found)?;
+                Ok(Some(Type::Bool))
+            }
+            Value::PreIncrement(ref lvalue) | Value::PreDecrement(ref lvalue)
+            | Value::PostIncrement(ref lvalue) | Value::PostDecrement(ref lvalue) => {
+                let found = self.lvalue(lvalue)?;
+                expect(Some(Type::Number), found)?;
+                Ok(Some(Type::Number))
+            }
+            Value::Placeholder => Ok(None),
+        }
+    }
+
+    /// The type of a variable. Only local variables and enum members have known types
+    fn lvalue(&mut self, lvalue: &LValue) -> Result<Option<Type>, Error> {
+        match lvalue {
+            LValue::Identifier(ref ident) => Ok(self.lookup(&ident.0)),
+            LValue::Global(..) => Ok(None),
+            LValue::Member(ref object, _) => {
+                if let LValue::Identifier(ref name) = **object {
+                    if self.symbols.enum_members(&name.0).is_some() {
+                        return Ok(Some(Type::Number));
+                    }
+                }
+                self.lvalue(object)?;
+                Ok(None)
+            }
+            LValue::Index(ref collection, ref accessor, ref indices) => {
+                let found = self.lvalue(collection)?;
+                expect(Some(accessed(accessor)), found).map_err(|error| error.at(collection.span()))?;
+                for index in &indices.0 {
+                    self.expression(index)?;
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// The type returned by a call, checking its arguments against the function being called. A piped
+    /// value goes in place of the placeholder, or else before the other arguments
+    fn call(&mut self, call: &Call, piped: Option<(Option<Type>, Span)>) -> Result<Option<Type>, Error> {
+        let mut arguments = vec![];
+        for argument in &call.arguments.0 {
+            arguments.push(match (argument, piped) {
+                (Expression::Value(Value::Placeholder, _), Some((piped, span))) => (piped, span),
+                (argument, _) => (self.expression(argument)?, argument.span()),
+            });
+        }
+        let placeholder = call.arguments.0.iter().any(|argument| match argument {
+            Expression::Value(Value::Placeholder, _) => true,
+            _ => false,
+        });
+        if let (Some(piped), false) = (piped, placeholder) {
+            arguments.insert(0, piped);
+        }
+        if !self.symbols.is_function(&call.name.0) {
+            self.warnings.push(Warning::UnknownFunction(call.span));
+        }
+        match self.symbols.signature(&call.name.0) {
+            // what the function returns is known whatever it is given, so checking carries on past
+            // any problems with the arguments
+            Some(signature) => {
+                let function = || call.name.0.clone();
+                let found = arguments.len();
+                if found < signature.required {
+                    self.errors.push(Error::TypeError(TypeError::TooFewArguments { function: function(), required: signature.required, found }).at(call.span));
+                }
+                if let Some(allowed) = signature.allowed() {
+                    if found > allowed {
+                        self.errors.push(Error::TypeError(TypeError::TooManyArguments { function: function(), allowed, found }).at(call.span));
+                    }
+                }
+                for (index, (found, span)) in arguments.into_iter().enumerate() {
+                    if let Err(error) = expect(signature.parameter(index), found) {
+                        self.errors.push(error.at(span));
+                    }
+                }
+                Ok(signature.returns)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// The type of a match, which is only known when every arm is an expression of the same type
+    fn matching(&mut self, expr: &Match) -> Result<Option<Type>, Error> {
+        self.expression(&expr.value)?;
+        let mut types = vec![];
+        for arm in &expr.arms {
+            for pattern in &arm.patterns {
+                if let Pattern::Value(ref value) = pattern {
+                    self.value(value)?;
+                }
+            }
+            types.push(match arm.body.kind {
+                StatementKind::Expression(ref value) => self.expression(value)?,
+                _ => {
+                    self.statement(&arm.body);
+                    None
+                }
+            });
+        }
+        match types.first() {
+            Some(&first) if types.iter().all(|&ty| ty == first) => Ok(first),
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gmlpp::AST;
+    use symbols::Symbols;
+    use error::{Error, Warning};
+    use super::check;
+
+    fn parse(source: &str) -> AST {
+        let ast = AST::from_reader(source.as_bytes()).unwrap();
+        assert!(ast.errors().is_empty(), "{:?}", ast.errors());
+        ast
+    }
+
+    /// Checks a file in a project which has a script `add`, taking two numbers and optionally a third
+    fn checked(source: &str) -> Result<Vec<Warning>, Vec<Error>> {
+        let mut symbols = Symbols::new();
+        let script = parse("argument a: Number\nargument b: Number\nargument c?: Number\nreturn: Number\n\nreturn a + b\n");
+        symbols.collect(Some("add".to_owned()), &script);
+        check(&parse(source), &symbols)
+    }
+
+    #[test]
+    fn well_typed_code_passes() {
+        assert!(checked("let a: Number = 1\nlet b: String = \"b\" + \"c\"\nif (a > 0) {\n    a += 1\n}\n").is_ok());
+    }
+
+    #[test]
+    fn value_of_the_wrong_type_cannot_be_assigned() {
+        assert_eq!(errors("let a: Number = 1\na = \"a\"\n"), vec![("type-mismatch", 2)]);
+    }
+
+    #[test]
+    fn returned_value_must_have_the_declared_type() {
+        assert_eq!(errors("return: Number\n\nreturn \"a\"\n"), vec![("type-mismatch", 3)]);
+    }
+
+    #[test]
+    fn sprite_cannot_be_played_as_a_sound() {
+        assert_eq!(errors("let s: Sprite = spr_player\naudio_play_sound(s, 1, false)\n"), vec![("type-mismatch", 2)]);
+    }
+
+    #[test]
+    fn instance_can_be_given_where_an_object_is_expected() {
+        let source = "let wall: Number = instance_nearest(x, y, obj_wall)\nif (place_meeting(x + 1, y, wall)) {\n    x -= 1\n}\n";
+        assert!(checked(source).is_ok());
+    }
+
+    #[test]
+    fn built_in_function_given_too_many_arguments_is_rejected() {
+        assert_eq!(errors("x = abs(1, 2)\n"), vec![("too-many-arguments", 1)]);
+    }
+
+    #[test]
+    fn unknown_function_is_warned_about() {
+        assert_eq!(checked("x = frobnicate(1)\n").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn script_given_too_few_arguments_is_rejected() {
+        assert_eq!(errors("x = add(1)\n"), vec![("too-few-arguments", 1)]);
+    }
+
+    #[test]
+    fn script_given_too_many_arguments_is_rejected() {
+        assert_eq!(errors("x = add(1, 2, 3, 4)\n"), vec![("too-many-arguments", 1)]);
+    }
+
+    #[test]
+    fn optional_arguments_of_a_script_can_be_left_out() {
+        assert!(checked("x = add(1, 2) + add(1, 2, 3)\n").is_ok());
+    }
+
+    /// The code and line of each error
+    fn errors(source: &str) -> Vec<(&'static str, usize)> {
+        checked(source)
+            .unwrap_err()
+            .iter()
+            .map(|error| (error.code(), error.span().unwrap().start.line))
+            .collect()
+    }
+
+    #[test]
+    fn every_type_error_is_reported() {
+        let source = "let a: Number = \"a\"\nlet b: String = 2\nif (b) { a = b }\n";
+        assert_eq!(
+            errors(source),
+            vec![("type-mismatch", 1), ("type-mismatch", 2), ("type-mismatch", 3), ("type-mismatch", 3)],
+        );
+    }
+
+    #[test]
+    fn checking_carries_on_after_a_statement_which_cannot_be_typed() {
+        let source = "let a: String = \"a\"\nlet b: Number = -a\nlet c: Bool = \"c\"\n";
+        assert_eq!(errors(source), vec![("type-mismatch", 2), ("type-mismatch", 3)]);
+    }
+
+    #[test]
+    fn every_bad_script_call_is_reported() {
+        let source = "x = add(1)\ny = add(1, 2, 3, 4)\nz = add(1, 2) + add(\"a\", 2)\n";
+        assert_eq!(
+            errors(source),
+            vec![("too-few-arguments", 1), ("too-many-arguments", 2), ("type-mismatch", 3)],
+        );
+    }
+
+    #[test]
+    fn every_bad_call_in_a_statement_is_reported() {
+        assert_eq!(errors("x = add(add(1), 2, 3, 4)\n"), vec![("too-few-arguments", 1), ("too-many-arguments", 1)]);
+    }
+
+    #[test]
+    fn every_error_in_a_switch_is_reported() {
+        let source = "switch (x) {\n    case \"a\" - 1:\n        y = 1\n    case 2:\n        z = \"b\" - 1\n}\n";
+        assert_eq!(errors(source), vec![("type-mismatch", 2), ("type-mismatch", 5)]);
+    }
+}
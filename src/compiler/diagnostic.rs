@@ -0,0 +1,231 @@
+//! Describes errors and warnings, either rendered the way rustc does, quoting the line of source they
+//! happened on, or as JSON for editors and other tools to read
+
+use std::error::Error as StdError;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde_json;
+
+use gmlpp::Span;
+use error::{Error, Warning};
+
+/// How diagnostics are written out
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MessageFormat {
+    /// Readable text, on stderr
+    Human,
+    /// One JSON object per line, on stdout
+    Json,
+}
+
+impl Default for MessageFormat {
+    fn default() -> Self {
+        MessageFormat::Human
+    }
+}
+
+impl FromStr for MessageFormat {
+    type Err = Error;
+    fn from_str(string: &str) -> Result<Self, Error> {
+        match string {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            _ => Err(Error::UnknownMessageFormat(string.to_owned())),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found in a file
+#[derive(Clone, Debug, Serialize)]
+pub struct Diagnostic {
+    file: PathBuf,
+    span: Option<Span>,
+    severity: Severity,
+    code: &'static str,
+    message: String,
+    /// Explains what is wrong with the code the span points to
+    label: Option<String>,
+    /// What was being done when the problem was found
+    notes: Vec<String>,
+    suggestion: Option<&'static str>,
+}
+
+impl Diagnostic {
+    /// Describes an error in a file
+    pub fn error(path: &Path, error: &Error) -> Self {
+        // type errors are explained in full under the code they point to
+        let (message, label) = match error.inner() {
+            Error::TypeError(ref error) => (error.description().to_owned(), Some(error.to_string())),
+            inner => (inner.to_string(), None),
+        };
+        Diagnostic {
+            file: error.file().unwrap_or(path).to_owned(),
+            span: error.span(),
+            severity: Severity::Error,
+            code: error.code(),
+            message,
+            label,
+            notes: error.contexts().into_iter().map(str::to_owned).collect(),
+            suggestion: error.suggestion(),
+        }
+    }
+
+    /// Describes a warning in a file
+    pub fn warning(path: &Path, warning: &Warning) -> Self {
+        Diagnostic {
+            file: path.to_owned(),
+            span: Some(warning.span()),
+            severity: Severity::Warning,
+            code: warning.code(),
+            message: warning.description().to_owned(),
+            label: None,
+            notes: vec![],
+            suggestion: warning.suggestion(),
+        }
+    }
+
+    /// Writes this diagnostic in a format
+    pub fn format(&self, format: MessageFormat, source: &str) -> String {
+        match format {
+            MessageFormat::Human => self.render(source),
+            MessageFormat::Json => serde_json::to_string(self).unwrap(),
+        }
+    }
+
+    /// Renders this diagnostic the way rustc does, underlining where it happened if that is known
+    pub fn render(&self, source: &str) -> String {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let mut output = format!("{}: {}\n", severity, self.message);
+        let gutter = self.span.map_or(0, |span| span.start.line.to_string().len());
+        match self.span {
+            Some(span) => self.quote(&mut output, source, span),
+            None => write!(output, " --> {}", self.file.display()).unwrap(),
+        }
+        for note in &self.notes {
+            write!(output, "\n{} = note: {}", " ".repeat(gutter), note).unwrap();
+        }
+        if let Some(suggestion) = self.suggestion {
+            write!(output, "\n{} = help: {}", " ".repeat(gutter), suggestion).unwrap();
+        }
+        output
+    }
+
+    /// Quotes the line of source the span starts on, and underlines the span
+    fn quote(&self, output: &mut String, source: &str, span: Span) {
+        let start = span.start;
+        let line = source.lines().nth(start.line - 1).unwrap_or("");
+        let gutter = " ".repeat(start.line.to_string().len());
+        writeln!(output, "{}--> {}:{}", gutter, self.file.display(), start).unwrap();
+        writeln!(output, "{} |", gutter).unwrap();
+        writeln!(output, "{} | {}", start.line, line).unwrap();
+        // tabs are kept so the underline lines up however wide they are shown
+        let indent: String = line
+            .chars()
+            .take(start.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let width = if span.end.line == start.line {
+            span.end.column.saturating_sub(start.column)
+        } else {
+            line.chars().count().saturating_sub(start.column - 1)
+        };
+        write!(output, "{} | {}{}", gutter, indent, "^".repeat(width.max(1))).unwrap();
+        if let Some(ref label) = self.label {
+            write!(output, " {}", label).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use gmlpp::AST;
+    use checker;
+    use symbols::Symbols;
+    use error::{Error, Warning};
+    use super::{Diagnostic, MessageFormat};
+
+    /// The first error found checking a file
+    fn checked(source: &str) -> Error {
+        let ast = AST::from_reader(source.as_bytes()).unwrap();
+        checker::check(&ast, &Symbols::new()).unwrap_err().remove(0)
+    }
+
+    #[test]
+    fn error_is_rendered_with_its_line_underlined() {
+        let source = "let a: Number = 1\nif (a > 0) {\n\ta = \"a\"\n}\n";
+        assert_eq!(
+            Diagnostic::error(Path::new("scripts/a.gmlpp"), &checked(source)).render(source),
+            concat!(
+                "error: Value does not have the type that is expected here\n",
+                " --> scripts/a.gmlpp:3:6\n",
+                "  |\n",
+                "3 | \ta = \"a\"\n",
+                "  | \t    ^^^ expected Number, found String",
+            ),
+        );
+    }
+
+    #[test]
+    fn error_is_rendered_with_what_was_being_done_and_in_which_file() {
+        let source = "x = 1\nx = \"a\" - 1\n";
+        let failure = checked(source).context("while checking a").in_file("objects/o/Create_0.gmlpp");
+        assert_eq!(
+            Diagnostic::error(Path::new("scripts/a.gmlpp"), &failure).render(source),
+            concat!(
+                "error: Value does not have the type that is expected here\n",
+                " --> objects/o/Create_0.gmlpp:2:5\n",
+                "  |\n",
+                "2 | x = \"a\" - 1\n",
+                "  |     ^^^ expected Number, found String\n",
+                "  = note: while checking a",
+            ),
+        );
+    }
+
+    #[test]
+    fn error_can_be_written_as_json() {
+        let source = "let a: Number = \"a\"\n";
+        assert_eq!(
+            Diagnostic::error(Path::new("a.gmlpp"), &checked(source)).format(MessageFormat::Json, source),
+            "{\"file\":\"a.gmlpp\",\"span\":{\"start\":{\"line\":1,\"column\":17},\"end\":{\"line\":1,\"column\":20}},\"severity\":\"error\",\"code\":\"type-mismatch\",\"message\":\"Value does not have the type that is expected here\",\"label\":\"expected Number, found String\",\"notes\":[],\"suggestion\":null}",
+        );
+    }
+
+    #[test]
+    fn message_format_is_read_from_its_name() {
+        assert_eq!("human".parse::<MessageFormat>().unwrap(), MessageFormat::Human);
+        assert_eq!("json".parse::<MessageFormat>().unwrap(), MessageFormat::Json);
+        assert!("xml".parse::<MessageFormat>().is_err());
+    }
+
+    #[test]
+    fn warning_is_rendered_with_its_line_underlined() {
+        let source = "x = 1\nfrobnicate(x)\n";
+        let ast = AST::from_reader(source.as_bytes()).unwrap();
+        let warning: Warning = checker::check(&ast, &Symbols::new()).unwrap().remove(0);
+        assert_eq!(
+            Diagnostic::warning(Path::new("a.gmlpp"), &warning).render(source),
+            concat!(
+                "warning: Function is not built in or a script in this project\n",
+                " --> a.gmlpp:2:1\n",
+                "  |\n",
+                "2 | frobnicate(x)\n",
+                "  | ^^^^^^^^^^^^^",
+            ),
+        );
+    }
+}
@@ -1,24 +1,36 @@
-use std::fs::File;
-use std::io::Write;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use std::sync::mpsc::channel;
+use std::cell::RefCell;
 
 use notify::{RecommendedWatcher, Watcher, RecursiveMode, DebouncedEvent};
 
-use gmlpp::AST;
+use gml;
+use gmlpp;
+use checker;
 use project::{Project, Source};
-use error::Error;
+use symbols::Symbols;
+use error::{Error, Warning};
+
+mod diagnostic;
+
+pub use self::diagnostic::MessageFormat;
+use self::diagnostic::Diagnostic;
 
 /// Performs compilation of `.gmlpp` files within a project
 #[derive(Clone, Debug)]
 pub struct Compiler {
     project: Project,
+    symbols: RefCell<Symbols>,
+    message_format: MessageFormat,
 }
 
 impl Compiler {
-    /// Creates a new instance of the Compiler, linked to a project
-    pub fn new(project: Project) -> Self {
-        Self{ project }
+    /// Creates a new instance of the Compiler, linked to a project, which reports problems in a format
+    pub fn new(project: Project, message_format: MessageFormat) -> Self {
+        Self{ project, symbols: RefCell::new(Symbols::new()), message_format }
     }
 
     /// Watches the project files, compiling the gmlpp files to gml
@@ -35,38 +47,235 @@ impl Compiler {
         Ok(())
     }
 
-    /// Handles a notification received from the watcher
+    /// Handles a notification received from the watcher. Only changes to `.gmlpp` files matter, as
+    /// compiling writes the `.gml` files which would otherwise set off another compile
     fn handle(&self, event: DebouncedEvent) -> Result<(), Error> {
         match event {
-            DebouncedEvent::Write(path) => self.compile(Source::from(path)),
+            DebouncedEvent::Write(path) if is_gmlpp(&path) => self.compile(self.source(path)).map(|_| ()),
             _ => Ok(()),
         }
     }
 
-    /// Compiles all the `.gmlpp` files in the project
-    pub fn compile_all(&self) -> Result<(), Error> {
+    /// Finds the source a changed file belongs to. The project knows where the code of sources
+    /// without a `.gml` file goes, so it is asked first
+    fn source(&self, path: PathBuf) -> Source {
+        let path = fs::canonicalize(&path).unwrap_or(path);
+        self.project
+            .sources()
+            .into_iter()
+            .find(|source| fs::canonicalize(source.gmlpp()).map_or(false, |gmlpp| gmlpp == path))
+            .unwrap_or_else(|| Source::from(path))
+    }
+
+    /// Compiles all the `.gmlpp` files in the project, returning whether they all compiled without
+    /// errors
+    pub fn compile_all(&self) -> Result<bool, Error> {
+        let sources = self.project.sources();
+        self.collect_symbols(&sources)?;
+        let mut succeeded = true;
+        for source in sources {
+            succeeded &= self.compile(source)?;
+        }
+        Ok(succeeded)
+    }
+
+    /// Parses and checks all the `.gmlpp` files in the project without writing any `.gml` files,
+    /// returning whether they are all free of errors
+    pub fn check_all(&self) -> Result<bool, Error> {
+        let sources = self.project.sources();
+        self.collect_symbols(&sources)?;
+        let mut succeeded = true;
+        for source in sources {
+            succeeded &= self.check(&source)?.is_some();
+        }
+        Ok(succeeded)
+    }
+
+    /// Rewrites all the `.gmlpp` files in the project in the standard style, returning whether they
+    /// could all be formatted. Only the doc comments at the top of a file are kept when it is
+    /// formatted, so files which have any others are left alone
+    pub fn format_all(&self) -> Result<bool, Error> {
+        let mut succeeded = true;
+        for source in self.project.sources() {
+            if !source.gmlpp().exists() {
+                continue;
+            }
+            let text = read(&source)?;
+            let parsed = match gmlpp::AST::from_reader(text.as_bytes()) {
+                Ok(ast) => if ast.errors().is_empty() { Ok(ast) } else { Err(ast.into_errors()) },
+                Err(error) => Err(vec![error]),
+            };
+            let ast = match parsed {
+                Ok(ast) => ast,
+                Err(errors) => {
+                    self.emit_errors(&source, &text, errors);
+                    succeeded = false;
+                    continue;
+                }
+            };
+            if ast.has_comments() {
+                eprintln!("Skipping source, as formatting would remove its comments: {:?}", source);
+                continue;
+            }
+            let formatted = ast.print();
+            if formatted != text {
+                if self.message_format == MessageFormat::Human {
+                    println!("Formatting source: {:?}", source);
+                }
+                fs::write(source.gmlpp(), formatted).map_err(|error| Error::from(error).in_file(source.gmlpp()))?;
+            }
+        }
+        Ok(succeeded)
+    }
+
+    /// Removes the `.gml` files generated from the `.gmlpp` files in the project
+    pub fn clean(&self) -> Result<(), Error> {
         for source in self.project.sources() {
-            self.compile(source)?;
+            if source.gmlpp().exists() && source.gml().exists() {
+                if self.message_format == MessageFormat::Human {
+                    println!("Removing output: {:?}", source.gml());
+                }
+                fs::remove_file(source.gml()).map_err(|error| Error::from(error).in_file(source.gml()))?;
+            }
         }
         Ok(())
     }
 
-    /// Compiles a `.gmlpp` file to it's corresponding `.gml` file
-    fn compile(&self, source: Source) -> Result<(), Error> {
-        if source.gmlpp().exists() {
-            println!("Compiling source: {:?}", source);
-            let file = File::open(source.gmlpp())?;
-            match AST::from_reader(file) {
-                Ok(ast) => {
-                    let mut outfile = File::create(source.gml())?;
-                    write!(outfile, "{}", ast.print())?;
-                }
-                Err(error) => {
-                    eprintln!("Error parsing file '{}': {:?}", source.gmlpp().to_string_lossy(), error);
+    /// Collects the declarations from every `.gmlpp` file, so they can be referenced from other files.
+    /// Whatever could be parsed of files with syntax errors is still collected, and the errors are
+    /// reported when they are compiled
+    fn collect_symbols(&self, sources: &Vec<Source>) -> Result<(), Error> {
+        let mut symbols = self.symbols.borrow_mut();
+        for source in sources {
+            if let Some(name) = source.script_name() {
+                symbols.declare_script(name);
+            }
+            if source.gmlpp().exists() {
+                let file = File::open(source.gmlpp()).map_err(|error| Error::from(error).in_file(source.gmlpp()))?;
+                if let Ok(ast) = gmlpp::AST::from_reader(file) {
+                    symbols.collect(source.script_name(), &ast);
                 }
             }
-            // do compile
         }
         Ok(())
     }
+
+    /// Compiles a `.gmlpp` file to it's corresponding `.gml` file, returning whether it compiled
+    /// without errors
+    fn compile(&self, source: Source) -> Result<bool, Error> {
+        if !source.gmlpp().exists() {
+            return Ok(true);
+        }
+        if self.message_format == MessageFormat::Human {
+            println!("Compiling source: {:?}", source);
+        }
+        match self.check(&source)? {
+            Some(ast) => match source.write(&ast.print()) {
+                Ok(()) => Ok(true),
+                // an event without anywhere to put its code is a problem with the project, not the compiler
+                Err(error) => match error.inner() {
+                    &Error::MissingCodeAction => {
+                        self.emit(Diagnostic::error(&source.gmlpp(), &error), "");
+                        Ok(false)
+                    }
+                    _ => Err(error),
+                },
+            },
+            None => Ok(false),
+        }
+    }
+
+    /// Parses, checks and lowers a `.gmlpp` file, reporting any problems with it. Nothing is
+    /// returned if it has errors
+    fn check(&self, source: &Source) -> Result<Option<gml::AST>, Error> {
+        if !source.gmlpp().exists() {
+            return Ok(None);
+        }
+        // the source is kept to quote in any diagnostics
+        let text = read(source)?;
+        match self.build(source, &text) {
+            Ok((warnings, ast)) => {
+                for warning in warnings.iter().chain(ast.warnings()) {
+                    self.emit(Diagnostic::warning(&source.gmlpp(), warning), &text);
+                }
+                Ok(Some(ast))
+            }
+            Err(errors) => {
+                self.emit_errors(source, &text, errors);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Reports the errors found in a file
+    fn emit_errors(&self, source: &Source, text: &str, errors: Vec<Error>) {
+        for error in errors {
+            self.emit(Diagnostic::error(&source.gmlpp(), &error), text);
+        }
+    }
+
+    /// Reports a problem found in a file
+    fn emit(&self, diagnostic: Diagnostic, source: &str) {
+        let output = diagnostic.format(self.message_format, source);
+        match self.message_format {
+            MessageFormat::Human => eprintln!("{}\n", output),
+            MessageFormat::Json => println!("{}", output),
+        }
+    }
+
+    /// Parses, checks and lowers the source of a `.gmlpp` file, returning every syntax error in it if
+    /// it cannot be parsed. Errors are marked with the file and script or event they happened in
+    fn build(&self, source: &Source, text: &str) -> Result<(Vec<Warning>, gml::AST), Vec<Error>> {
+        self.translate(source, text).map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|error| match source.description(&self.project) {
+                    Some(description) => error.context(format!("in {}", description)),
+                    None => error,
+                })
+                .map(|error| error.in_file(source.gmlpp()))
+                .collect()
+        })
+    }
+
+    /// Does the work of `build`, leaving its errors unmarked
+    fn translate(&self, source: &Source, text: &str) -> Result<(Vec<Warning>, gml::AST), Vec<Error>> {
+        let ast = gmlpp::AST::from_reader(text.as_bytes()).map_err(|error| vec![error])?;
+        self.symbols.borrow_mut().collect(source.script_name(), &ast);
+        if !ast.errors().is_empty() {
+            return Err(ast.into_errors());
+        }
+        let symbols = self.symbols.borrow();
+        let warnings = checker::check(&ast, &symbols)?;
+        let ast = gml::AST::from_gmlpp(&ast, &symbols).map_err(|error| vec![error])?;
+        Ok((warnings, ast))
+    }
+}
+
+/// Whether a path is that of a `.gmlpp` file
+fn is_gmlpp(path: &Path) -> bool {
+    path.extension().map_or(false, |extension| extension == "gmlpp")
+}
+
+/// Reads the `.gmlpp` file of a source
+fn read(source: &Source) -> Result<String, Error> {
+    let mut text = String::new();
+    File::open(source.gmlpp())
+        .and_then(|mut file| file.read_to_string(&mut text))
+        .map_err(|error| Error::from(error).in_file(source.gmlpp()))?;
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use super::is_gmlpp;
+
+    #[test]
+    fn only_gmlpp_files_are_compiled_when_changed() {
+        assert!(is_gmlpp(Path::new("scripts/scr_move/scr_move.gmlpp")));
+        assert!(!is_gmlpp(Path::new("scripts/scr_move/scr_move.gml")));
+        assert!(!is_gmlpp(Path::new("objects/obj_player/obj_player.yy")));
+        assert!(!is_gmlpp(Path::new("scripts/gmlpp")));
+    }
 }
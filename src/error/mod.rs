@@ -1,45 +1,234 @@
 use std::error;
 use std::fmt;
 use std::io;
-use std::path;
+use std::path::{Path, PathBuf};
 use std::num;
 
 mod parse_error;
+mod rewrite_error;
+mod type_error;
+mod warning;
 
 use serde_json;
 use notify;
+use xmltree;
+
+use gmlpp::{Span, Token};
 
 pub use self::parse_error::ParseError;
+pub use self::rewrite_error::RewriteError;
+pub use self::type_error::TypeError;
+pub use self::warning::Warning;
 
 #[derive(Debug)]
 pub enum Error {
-    ArgumentError,
-    NoProject,
-    InvalidCharacter,
-    UnexpectedCharacter,
-    MalformedNumericLiteral,
+    UnknownArgument(String),
+    UnknownMessageFormat(String),
+    ProjectNotFound,
+    AmbiguousProject(Vec<PathBuf>),
+    NoProject(PathBuf),
+    InvalidCharacter(char),
+    UnexpectedCharacter(char),
+    MalformedNumericLiteral(char),
     CommentNestingDepth,
-    InvalidPreprocessorDirective,
+    InvalidPreprocessorDirective(char),
     UnexpectedEOF,
     ParseError(ParseError),
-    MissingResource(String),
+    /// A syntax error where it is known which token was found instead of what was expected
+    UnexpectedToken { expected: ParseError, found: Token },
+    RewriteError(RewriteError),
+    TypeError(TypeError),
+    MissingResource(PathBuf),
     IOError(io::Error),
     JSONError(serde_json::Error),
+    XMLError(xmltree::ParseError),
+    XMLWriteError(xmltree::Error),
+    /// An event of a GameMaker Studio 1.4 object which has no code action to put compiled code in
+    MissingCodeAction,
     NotifyError(notify::Error),
     ParseFloatError(num::ParseFloatError),
     ParseIntError(num::ParseIntError),
     CharsError(io::CharsError),
+    /// An error which happened in a particular part of the source file
+    At(Span, Box<Error>),
+    /// An error which happened while doing something, such as parsing the arguments of a call
+    Context(String, Box<Error>),
+    /// An error which happened in a particular file
+    In(PathBuf, Box<Error>),
 }
 
 impl Error {
-    pub fn missing_resource(path: &path::Path) -> Self {
-        Error::MissingResource(format!("The resource is missing at path {:?}", path))
+    /// Records where this error happened, unless a more precise location is already known
+    pub fn at(self, span: Span) -> Self {
+        match self.span() {
+            Some(..) => self,
+            None => Error::At(span, box self),
+        }
+    }
+
+    /// Records which token was found where a syntax error happened, unless its location is already
+    /// known, in which case it was not this token that caused it
+    pub fn found(self, token: Token) -> Self {
+        match self {
+            Error::ParseError(expected) => Error::UnexpectedToken { expected, found: token },
+            Error::Context(context, error) => Error::Context(context, box (*error).found(token)),
+            error => error,
+        }
+    }
+
+    /// Describes what was being done when this error happened, such as "while parsing the argument
+    /// list"
+    pub fn context<S: Into<String>>(self, context: S) -> Self {
+        Error::Context(context.into(), box self)
+    }
+
+    /// Records which file this error happened in, unless it is already known
+    pub fn in_file<P: AsRef<Path>>(self, path: P) -> Self {
+        match self.file() {
+            Some(..) => self,
+            None => Error::In(path.as_ref().to_owned(), box self),
+        }
+    }
+
+    /// Where this error happened, if it is known
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            &Error::At(span, _) => Some(span),
+            &Error::Context(_, ref error) | &Error::In(_, ref error) => error.span(),
+            _ => None,
+        }
+    }
+
+    /// Which file this error happened in, if it is known
+    pub fn file(&self) -> Option<&Path> {
+        match self {
+            &Error::In(ref path, _) => Some(path),
+            &Error::At(_, ref error) | &Error::Context(_, ref error) => error.file(),
+            _ => None,
+        }
+    }
+
+    /// What was being done when this error happened, starting from the most specific
+    pub fn contexts(&self) -> Vec<&str> {
+        match self {
+            &Error::Context(ref context, ref error) => {
+                let mut contexts = error.contexts();
+                contexts.push(context);
+                contexts
+            }
+            &Error::At(_, ref error) | &Error::In(_, ref error) => error.contexts(),
+            _ => vec![],
+        }
+    }
+
+    /// This error, without where it happened or what was being done at the time
+    pub fn inner(&self) -> &Error {
+        match self {
+            &Error::At(_, ref error) | &Error::Context(_, ref error) | &Error::In(_, ref error) => error.inner(),
+            error => error,
+        }
+    }
+
+    /// A short name for this kind of error, which tools can use to recognize it
+    pub fn code(&self) -> &'static str {
+        use self::Error::*;
+        match self.inner() {
+            &UnknownArgument(..) => "unknown-argument",
+            &ProjectNotFound => "project-not-found",
+            &AmbiguousProject(..) => "ambiguous-project",
+            &UnknownMessageFormat(..) => "unknown-message-format",
+            &NoProject(..) => "no-project",
+            &InvalidCharacter(..) => "invalid-character",
+            &UnexpectedCharacter(..) => "unexpected-character",
+            &MalformedNumericLiteral(..) => "malformed-numeric-literal",
+            &CommentNestingDepth => "comment-nesting-depth",
+            &InvalidPreprocessorDirective(..) => "invalid-preprocessor-directive",
+            &UnexpectedEOF => "unexpected-eof",
+            &ParseError(ref error) | &UnexpectedToken { expected: ref error, .. } => error.code(),
+            &RewriteError(ref error) => error.code(),
+            &TypeError(ref error) => error.code(),
+            &MissingResource(..) => "missing-resource",
+            &IOError(..) => "io-error",
+            &JSONError(..) => "json-error",
+            &XMLError(..) | &XMLWriteError(..) => "xml-error",
+            &MissingCodeAction => "missing-code-action",
+            &NotifyError(..) => "notify-error",
+            &ParseFloatError(..) => "parse-float-error",
+            &ParseIntError(..) => "parse-int-error",
+            &CharsError(..) => "chars-error",
+            &At(..) | &Context(..) | &In(..) => unreachable!(),
+        }
+    }
+
+    /// How this could be fixed, if there is an obvious way
+    pub fn suggestion(&self) -> Option<&'static str> {
+        use self::Error::*;
+        match self.inner() {
+            &ParseError(ref error) | &UnexpectedToken { expected: ref error, .. } => error.suggestion(),
+            &RewriteError(ref error) => error.suggestion(),
+            &MissingCodeAction => Some("add an Execute Code action to the event in GameMaker"),
+            _ => None,
+        }
+    }
+
+    pub fn missing_resource(path: &Path) -> Self {
+        Error::MissingResource(path.to_owned())
+    }
+
+    /// Describes this error on its own, without where it happened
+    fn message(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::Error::*;
+        use std::error::Error as StdError;
+        match self {
+            &UnknownArgument(ref argument) => write!(f, "Unknown argument `{}`. Run with `help` to see the usage", argument),
+            &AmbiguousProject(ref paths) => {
+                write!(f, "There are multiple project files, so please choose one of them:")?;
+                for path in paths {
+                    write!(f, "\n    {}", path.display())?;
+                }
+                Ok(())
+            }
+            &UnknownMessageFormat(ref format) =>
+                write!(f, "Unknown message format `{}`, expected `human` or `json`", format),
+            &NoProject(ref path) => write!(f, "The project file does not exist at {}", path.display()),
+            &InvalidCharacter(c) => write!(f, "Invalid character `{}` in source file", c.escape_debug()),
+            &UnexpectedCharacter(c) => write!(f, "Unexpected character `{}` in source file", c.escape_debug()),
+            &MalformedNumericLiteral(c) => write!(f, "Malformed numeric literal: unexpected `{}`", c.escape_debug()),
+            &InvalidPreprocessorDirective(c) =>
+                write!(f, "Invalid preprocessor directive: unexpected `{}`", c.escape_debug()),
+            &ParseError(ref error) => write!(f, "{}", error),
+            &UnexpectedToken { ref expected, ref found } => write!(f, "{}, found {}", expected, found),
+            &RewriteError(ref error) => write!(f, "{}", error),
+            &TypeError(ref error) => write!(f, "{}: {}", error.description(), error),
+            &MissingResource(ref path) => write!(f, "The resource is missing at path {}", path.display()),
+            &IOError(ref error) => write!(f, "{}", error),
+            &JSONError(ref error) => write!(f, "{}", error),
+            &XMLError(ref error) => write!(f, "{}", error),
+            &XMLWriteError(ref error) => write!(f, "{}", error),
+            &NotifyError(ref error) => write!(f, "{}", error),
+            &ParseFloatError(ref error) => write!(f, "{}", error),
+            &ParseIntError(ref error) => write!(f, "{}", error),
+            &CharsError(ref error) => write!(f, "{}", error),
+            &At(_, ref error) | &Context(_, ref error) | &In(_, ref error) => error.message(f),
+            error => write!(f, "{}", error.description()),
+        }
     }
 }
 
 impl fmt::Display for Error {
+    /// Writes the error as `path:line:column: message`, followed by what was being done at the time
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match (self.file(), self.span()) {
+            (Some(path), Some(span)) => write!(f, "{}:{}: ", path.display(), span)?,
+            (Some(path), None) => write!(f, "{}: ", path.display())?,
+            (None, Some(span)) => write!(f, "{}: ", span)?,
+            (None, None) => {}
+        }
+        self.message(f)?;
+        for context in self.contexts() {
+            write!(f, "\n    {}", context)?;
+        }
+        Ok(())
     }
 }
 
@@ -47,22 +236,31 @@ impl error::Error for Error {
     fn description(&self) -> &str {
         use self::Error::*;
         match self {
-            &ArgumentError => "Please supply the project file (.yyp)",
-            &NoProject => "The project file does not exist at the supplied path",
-            &InvalidCharacter => "Invalid character in source file",
-            &UnexpectedCharacter => "Unexpected character in source file",
-            &MalformedNumericLiteral => "Malformed numeric literal",
+            &UnknownArgument(..) => "Unknown argument",
+            &ProjectNotFound => "There is no project file (.yyp or .project.gmx) in this directory, so please supply one",
+            &AmbiguousProject(..) => "There are multiple project files (.yyp or .project.gmx) in this directory, so please supply one",
+            &UnknownMessageFormat(..) => "Unknown message format",
+            &NoProject(..) => "The project file does not exist at the supplied path",
+            &InvalidCharacter(..) => "Invalid character in source file",
+            &UnexpectedCharacter(..) => "Unexpected character in source file",
+            &MalformedNumericLiteral(..) => "Malformed numeric literal",
             &CommentNestingDepth => "Comment nesting is too deep",
-            &InvalidPreprocessorDirective => "Invalid preprocessor directive",
+            &InvalidPreprocessorDirective(..) => "Invalid preprocessor directive",
             &UnexpectedEOF => "Unexpected end of input",
-            &ParseError(ref error) => error.description(),
+            &ParseError(ref error) | &UnexpectedToken { expected: ref error, .. } => error.description(),
+            &RewriteError(ref error) => error.description(),
+            &TypeError(ref error) => error.description(),
             &ParseFloatError(ref error) => error.description(),
             &ParseIntError(ref error) => error.description(),
-            &MissingResource(ref message) => &message,
+            &MissingResource(..) => "The resource is missing",
             &IOError(ref error) => error.description(),
             &JSONError(ref error) => error.description(),
+            &XMLError(ref error) => error.description(),
+            &XMLWriteError(ref error) => error.description(),
+            &MissingCodeAction => "The event has no code action to put the compiled code in",
             &NotifyError(ref error) => error.description(),
             &CharsError(ref error) => error.description(),
+            &At(_, ref error) | &Context(_, ref error) | &In(_, ref error) => (**error).description(),
         }
     }
 }
@@ -73,6 +271,18 @@ impl From<ParseError> for Error {
     }
 }
 
+impl From<RewriteError> for Error {
+    fn from(other: RewriteError) -> Error {
+        Error::RewriteError(other)
+    }
+}
+
+impl From<TypeError> for Error {
+    fn from(other: TypeError) -> Error {
+        Error::TypeError(other)
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(other: io::Error) -> Error {
         Error::IOError(other)
@@ -85,6 +295,18 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<xmltree::ParseError> for Error {
+    fn from(other: xmltree::ParseError) -> Error {
+        Error::XMLError(other)
+    }
+}
+
+impl From<xmltree::Error> for Error {
+    fn from(other: xmltree::Error) -> Error {
+        Error::XMLWriteError(other)
+    }
+}
+
 impl From<notify::Error> for Error {
     fn from(other: notify::Error) -> Error {
         Error::NotifyError(other)
@@ -108,3 +330,49 @@ impl From<io::CharsError> for Error {
         Error::CharsError(other)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gmlpp::AST;
+    use super::Error;
+
+    /// The syntax error which stops a file from being parsed at all
+    fn syntax_error(source: &str) -> Error {
+        AST::from_reader(source.as_bytes()).unwrap_err()
+    }
+
+    #[test]
+    fn syntax_error_names_the_token_found_and_what_was_being_parsed() {
+        assert_eq!(
+            syntax_error("argument 1\n").to_string(),
+            concat!(
+                "1:10: Expected identifier, found `1`\n",
+                "    while parsing the argument list",
+            ),
+        );
+    }
+
+    #[test]
+    fn error_is_described_with_the_file_it_happened_in_and_every_context() {
+        let error = syntax_error("argument a\nargument 1\n")
+            .context("while compiling scr_move")
+            .in_file("scripts/scr_move/scr_move.gmlpp");
+        assert_eq!(
+            error.to_string(),
+            concat!(
+                "scripts/scr_move/scr_move.gmlpp:2:10: Expected identifier, found `1`\n",
+                "    while parsing the argument list\n",
+                "    while compiling scr_move",
+            ),
+        );
+    }
+
+    #[test]
+    fn most_precise_location_is_kept() {
+        let inner = syntax_error("argument 1\n");
+        let outer = syntax_error("argument a\nargument 1\n");
+        let error = inner.at(outer.span().unwrap()).in_file("a.gmlpp").in_file("b.gmlpp");
+        assert_eq!(error.span().map(|span| span.start.line), Some(1));
+        assert_eq!(error.file().unwrap().to_str(), Some("a.gmlpp"));
+    }
+}
@@ -14,11 +14,67 @@ pub enum ParseError {
     IncompleteTernaryOperator,
     MismatchedParentheses,
     ExpectedEndOfStatement,
+    ExpectedAssignment,
+    ExpectedMember,
+    MismatchedBrackets,
+    WrongNumberOfIndices,
+    ExpectedBlock,
+    ExpectedCase,
+    ExpectedColon,
+    ExpectedComma,
+    ExpectedArrow,
+    ExpectedType,
+    ExpectedLoopInitializer,
+}
+
+impl ParseError {
+    /// A short name for this kind of syntax error, which tools can use to recognize it
+    pub fn code(&self) -> &'static str {
+        use self::ParseError::*;
+        match self {
+            ExpectedValue => "expected-value",
+            ExpectedLiteral => "expected-literal",
+            ExpectedIdentifier => "expected-identifier",
+            ExpectedFunctionCall => "expected-function-call",
+            ExpectedArgument => "expected-argument",
+            ExpectedKeyword => "expected-keyword",
+            ExpectedStatement => "expected-statement",
+            ExpectedParentheses => "expected-parentheses",
+            IncompleteTernaryOperator => "incomplete-ternary-operator",
+            MismatchedParentheses => "mismatched-parentheses",
+            ExpectedEndOfStatement => "expected-end-of-statement",
+            ExpectedAssignment => "expected-assignment",
+            ExpectedMember => "expected-member",
+            MismatchedBrackets => "mismatched-brackets",
+            WrongNumberOfIndices => "wrong-number-of-indices",
+            ExpectedBlock => "expected-block",
+            ExpectedCase => "expected-case",
+            ExpectedColon => "expected-colon",
+            ExpectedComma => "expected-comma",
+            ExpectedArrow => "expected-arrow",
+            ExpectedType => "expected-type",
+            ExpectedLoopInitializer => "expected-loop-initializer",
+        }
+    }
+
+    /// How this could be fixed, if there is an obvious way
+    pub fn suggestion(&self) -> Option<&'static str> {
+        use self::ParseError::*;
+        match self {
+            MismatchedParentheses => Some("Add a `)` to close the parentheses"),
+            MismatchedBrackets => Some("Add a `]` to close the brackets"),
+            ExpectedEndOfStatement => Some("Add a `;` or line break to end the statement"),
+            ExpectedColon => Some("Add a `:`"),
+            ExpectedComma => Some("Add a `,`"),
+            ExpectedArrow => Some("Add a `=>`"),
+            _ => None,
+        }
+    }
 }
 
 impl Display for ParseError {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        write!(f, "{}", self.description())
     }
 }
 
@@ -37,6 +93,17 @@ impl Error for ParseError {
             IncompleteTernaryOperator => "Incomplete ternary operator",
             MismatchedParentheses => "Mismatched parentheses",
             ExpectedEndOfStatement => "Expected end of statement",
+            ExpectedAssignment => "Expected assignment operator",
+            ExpectedMember => "Expected member name",
+            MismatchedBrackets => "Mismatched brackets",
+            WrongNumberOfIndices => "Wrong number of indices for this accessor",
+            ExpectedBlock => "Expected block",
+            ExpectedCase => "Expected case",
+            ExpectedColon => "Expected colon",
+            ExpectedComma => "Expected comma",
+            ExpectedArrow => "Expected `=>`",
+            ExpectedType => "Expected type",
+            ExpectedLoopInitializer => "Expected a declaration, assignment or expression to start the loop",
         }
     }
 }
@@ -0,0 +1,74 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug)]
+pub enum RewriteError {
+    MisplacedPlaceholder,
+    MultiplePlaceholders,
+    UnknownEnumMember,
+    NonExhaustiveMatch,
+    MatchArmNotExpression,
+    HoistInLoopHeader,
+    HoistInConditionalExpression,
+    UnsupportedLoopHeader,
+    Redeclaration,
+    AssignmentToConstant,
+}
+
+impl RewriteError {
+    /// A short name for this kind of error, which tools can use to recognize it
+    pub fn code(&self) -> &'static str {
+        use self::RewriteError::*;
+        match self {
+            MisplacedPlaceholder => "misplaced-placeholder",
+            MultiplePlaceholders => "multiple-placeholders",
+            UnknownEnumMember => "unknown-enum-member",
+            NonExhaustiveMatch => "non-exhaustive-match",
+            MatchArmNotExpression => "match-arm-not-expression",
+            HoistInLoopHeader => "hoist-in-loop-header",
+            HoistInConditionalExpression => "hoist-in-conditional-expression",
+            UnsupportedLoopHeader => "unsupported-loop-header",
+            Redeclaration => "redeclaration",
+            AssignmentToConstant => "assignment-to-constant",
+        }
+    }
+
+    /// How this could be fixed, if there is an obvious way
+    pub fn suggestion(&self) -> Option<&'static str> {
+        use self::RewriteError::*;
+        match self {
+            NonExhaustiveMatch => Some("Add a `_` arm to handle the rest"),
+            UnsupportedLoopHeader => Some("Move it to before the loop, or to the end of its body"),
+            HoistInConditionalExpression => Some("Assign it to a variable first, or use an `if` statement"),
+            Redeclaration => Some("Assign to the variable instead, or give the new one another name"),
+            AssignmentToConstant => Some("Declare it with `let` instead of `const` if it needs to change"),
+            _ => None,
+        }
+    }
+}
+
+impl Display for RewriteError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
+impl Error for RewriteError {
+    fn description(&self) -> &str {
+        use self::RewriteError::*;
+        match self {
+            MisplacedPlaceholder => "Placeholder can only be used as an argument to the right of a pipe",
+            MultiplePlaceholders => "Only one placeholder can be used in each call in a pipe",
+            UnknownEnumMember => "Enum does not have a member with this name",
+            NonExhaustiveMatch => "Match does not cover every value",
+            MatchArmNotExpression => "Every arm of a match used as a value must be an expression",
+            HoistInLoopHeader =>
+                "This expression needs statements to run before it, so cannot be used in the header of a loop",
+            UnsupportedLoopHeader => "This statement cannot be written in the header of a loop in GML",
+            HoistInConditionalExpression =>
+                "This expression needs statements to run before it, so cannot be used where it is only evaluated some of the time",
+            Redeclaration => "Variable is already declared",
+            AssignmentToConstant => "Cannot assign to a constant",
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use gmlpp::ast::Type;
+
+#[derive(Debug)]
+pub enum TypeError {
+    Mismatch { expected: Type, found: Type },
+    TooFewArguments { function: String, required: usize, found: usize },
+    TooManyArguments { function: String, allowed: usize, found: usize },
+}
+
+impl TypeError {
+    /// A short name for this kind of error, which tools can use to recognize it
+    pub fn code(&self) -> &'static str {
+        use self::TypeError::*;
+        match self {
+            Mismatch { .. } => "type-mismatch",
+            TooFewArguments { .. } => "too-few-arguments",
+            TooManyArguments { .. } => "too-many-arguments",
+        }
+    }
+}
+
+impl Display for TypeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TypeError::Mismatch { expected, found } => write!(f, "expected {}, found {}", expected, found),
+            TypeError::TooFewArguments { function, required, found } =>
+                write!(f, "{} takes at least {} arguments, but {} were given", function, required, found),
+            TypeError::TooManyArguments { function, allowed, found } =>
+                write!(f, "{} takes at most {} arguments, but {} were given", function, allowed, found),
+        }
+    }
+}
+
+impl Error for TypeError {
+    fn description(&self) -> &str {
+        match self {
+            TypeError::Mismatch { .. } => "Value does not have the type that is expected here",
+            TypeError::TooFewArguments { .. } => "Function is called without all of its required arguments",
+            TypeError::TooManyArguments { .. } => "Function is called with more arguments than it accepts",
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt::{self, Display, Formatter};
+
+use gmlpp::Span;
+
+/// Problems which do not stop a file from compiling, but probably should be fixed
+#[derive(Debug)]
+pub enum Warning {
+    ImplicitFallthrough(Span),
+    UnreachableMatchArm(Span),
+    UnknownFunction(Span),
+}
+
+impl Warning {
+    /// A short name for this kind of warning, which tools can use to recognize it
+    pub fn code(&self) -> &'static str {
+        use self::Warning::*;
+        match self {
+            ImplicitFallthrough(..) => "implicit-fallthrough",
+            UnreachableMatchArm(..) => "unreachable-match-arm",
+            UnknownFunction(..) => "unknown-function",
+        }
+    }
+
+    /// Where in the file this warning is about
+    pub fn span(&self) -> Span {
+        use self::Warning::*;
+        match *self {
+            ImplicitFallthrough(span) | UnreachableMatchArm(span) | UnknownFunction(span) => span,
+        }
+    }
+
+    /// How this could be fixed, if there is an obvious way
+    pub fn suggestion(&self) -> Option<&'static str> {
+        use self::Warning::*;
+        match self {
+            ImplicitFallthrough(..) => Some("Add a `// fallthrough` comment if this is intentional"),
+            UnreachableMatchArm(..) => Some("Remove the arm, or move it before the one that matches everything"),
+            UnknownFunction(..) => None,
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        use self::Warning::*;
+        match self {
+            ImplicitFallthrough(..) => "Case falls through to the next case",
+            UnreachableMatchArm(..) => "Match arm can never be reached, because an earlier arm matches everything",
+            UnknownFunction(..) => "Function is not built in or a script in this project",
+        }
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
@@ -0,0 +1,481 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A complete `.gml` script or event
+#[derive(Clone, Debug)]
+pub struct Code {
+    pub docs: Vec<String>,
+    pub body: Vec<Statement>,
+}
+
+impl Display for Code {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for line in &self.docs {
+            writeln!(f, "{}", line)?;
+        }
+        for statement in &self.body {
+            writeln!(f, "{}", statement)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Statement {
+    Expression(Expression),
+    Assignment(LValue, Expression),
+    CompoundAssignment(LValue, Operator, Expression),
+    VarDecl(String, Option<Expression>),
+    GlobalvarDecl(String),
+    If(Expression, Box<Statement>, Option<Box<Statement>>),
+    While(Expression, Box<Statement>),
+    DoUntil(Box<Statement>, Expression),
+    Repeat(Expression, Box<Statement>),
+    For(Simple, Expression, Simple, Box<Statement>),
+    With(Expression, Box<Statement>),
+    Block(Vec<Statement>),
+    Switch(Expression, Vec<Case>),
+    Enum(String, Vec<(String, Option<Expression>)>),
+    Return(Option<Expression>),
+    Break,
+    Continue,
+    Exit,
+}
+
+impl Statement {
+    /// Wraps a list of statements up as a single statement, only adding a block if required
+    pub fn from_vec(mut statements: Vec<Statement>) -> Self {
+        if statements.len() == 1 {
+            statements.pop().unwrap()
+        } else {
+            Statement::Block(statements)
+        }
+    }
+
+    fn is_block(&self) -> bool {
+        match self {
+            Statement::Block(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Writes the statements of a block, surrounded by braces. The opening brace is not indented
+    fn fmt_block(f: &mut Formatter, statements: &Vec<Statement>, indent: usize) -> fmt::Result {
+        writeln!(f, "{{")?;
+        for statement in statements {
+            writeln!(f, "{0:.1$}", statement, indent + 4)?;
+        }
+        write!(f, "{0:1$}}}", "", indent)
+    }
+
+    /// Writes the body of a control structure, keeping blocks on the same line as the header
+    fn fmt_body(f: &mut Formatter, body: &Statement, indent: usize) -> fmt::Result {
+        match body {
+            Statement::Block(ref statements) => {
+                write!(f, " ")?;
+                Self::fmt_block(f, statements, indent)
+            }
+            _ => write!(f, "\n{0:.1$}", body, indent + 4),
+        }
+    }
+}
+
+impl Display for Statement {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let indent = f.precision().unwrap_or(0);
+        write!(f, "{0:1$}", "", indent)?;
+        match self {
+            Statement::Expression(ref expr) => write!(f, "{};", expr),
+            Statement::Assignment(ref lvalue, ref expr) => write!(f, "{} = {};", lvalue, expr),
+            Statement::CompoundAssignment(ref lvalue, ref op, ref expr) => write!(f, "{} {}= {};", lvalue, op, expr),
+            Statement::VarDecl(ref name, None) => write!(f, "var {};", name),
+            Statement::VarDecl(ref name, Some(ref expr)) => write!(f, "var {} = {};", name, expr),
+            Statement::GlobalvarDecl(ref name) => write!(f, "globalvar {};", name),
+            Statement::If(ref cond, ref body, ref fbody) => {
+                write!(f, "if ({})", cond)?;
+                Self::fmt_body(f, body, indent)?;
+                match fbody {
+                    None => Ok(()),
+                    Some(ref fbody) => {
+                        if body.is_block() {
+                            write!(f, " else")?;
+                        } else {
+                            write!(f, "\n{0:1$}else", "", indent)?;
+                        }
+                        match **fbody {
+                            // else if chains stay at the same level of indentation
+                            Statement::If(..) => write!(f, " {}", format!("{0:.1$}", fbody, indent).trim_start()),
+                            _ => Self::fmt_body(f, fbody, indent),
+                        }
+                    }
+                }
+            }
+            Statement::While(ref cond, ref body) => {
+                write!(f, "while ({})", cond)?;
+                Self::fmt_body(f, body, indent)
+            }
+            Statement::DoUntil(ref body, ref cond) => {
+                write!(f, "do")?;
+                Self::fmt_body(f, body, indent)?;
+                if body.is_block() {
+                    write!(f, " until ({});", cond)
+                } else {
+                    write!(f, "\n{0:1$}until ({2});", "", indent, cond)
+                }
+            }
+            Statement::Repeat(ref times, ref body) => {
+                write!(f, "repeat ({})", times)?;
+                Self::fmt_body(f, body, indent)
+            }
+            Statement::For(ref init, ref cond, ref update, ref body) => {
+                write!(f, "for ({}; {}; {})", init, cond, update)?;
+                Self::fmt_body(f, body, indent)
+            }
+            Statement::With(ref target, ref body) => {
+                write!(f, "with ({})", target)?;
+                Self::fmt_body(f, body, indent)
+            }
+            Statement::Block(ref statements) => Self::fmt_block(f, statements, indent),
+            Statement::Switch(ref value, ref cases) => {
+                writeln!(f, "switch ({}) {{", value)?;
+                for case in cases {
+                    writeln!(f, "{0:.1$}", case, indent + 4)?;
+                }
+                write!(f, "{0:1$}}}", "", indent)
+            }
+            Statement::Enum(ref name, ref members) => {
+                writeln!(f, "enum {} {{", name)?;
+                for (i, (ref member, ref value)) in members.iter().enumerate() {
+                    write!(f, "{0:1$}{2}", "", indent + 4, member)?;
+                    if let Some(ref value) = value {
+                        write!(f, " = {}", value)?;
+                    }
+                    if i + 1 != members.len() {
+                        write!(f, ",")?;
+                    }
+                    writeln!(f)?;
+                }
+                write!(f, "{0:1$}}}", "", indent)
+            }
+            Statement::Return(None) => write!(f, "return;"),
+            Statement::Return(Some(ref value)) => write!(f, "return {};", value),
+            Statement::Break => write!(f, "break;"),
+            Statement::Continue => write!(f, "continue;"),
+            Statement::Exit => write!(f, "exit;"),
+        }
+    }
+}
+
+/// A statement which can be written in the header of a `for` loop, so has no semicolon of its own
+#[derive(Clone, Debug)]
+pub enum Simple {
+    Empty,
+    Expression(Expression),
+    Assignment(LValue, Expression),
+    CompoundAssignment(LValue, Operator, Expression),
+    VarDecl(String, Option<Expression>),
+}
+
+impl Simple {
+    /// Converts a statement to one that can be written in the header of a `for` loop, if it can be
+    pub fn from_statement(statement: Statement) -> Option<Self> {
+        match statement {
+            Statement::Expression(expr) => Some(Simple::Expression(expr)),
+            Statement::Assignment(lvalue, expr) => Some(Simple::Assignment(lvalue, expr)),
+            Statement::CompoundAssignment(lvalue, op, expr) => Some(Simple::CompoundAssignment(lvalue, op, expr)),
+            Statement::VarDecl(name, value) => Some(Simple::VarDecl(name, value)),
+            Statement::Block(ref statements) if statements.is_empty() => Some(Simple::Empty),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Simple {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Simple::Empty => Ok(()),
+            Simple::Expression(ref expr) => write!(f, "{}", expr),
+            Simple::Assignment(ref lvalue, ref expr) => write!(f, "{} = {}", lvalue, expr),
+            Simple::CompoundAssignment(ref lvalue, ref op, ref expr) => write!(f, "{} {}= {}", lvalue, op, expr),
+            Simple::VarDecl(ref name, None) => write!(f, "var {}", name),
+            Simple::VarDecl(ref name, Some(ref expr)) => write!(f, "var {} = {}", name, expr),
+        }
+    }
+}
+
+/// A labelled case of a switch statement. A `None` label is the default case
+#[derive(Clone, Debug)]
+pub struct Case {
+    pub label: Option<Expression>,
+    pub body: Vec<Statement>,
+}
+
+impl Display for Case {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let indent = f.precision().unwrap_or(0);
+        match self.label {
+            Some(ref label) => write!(f, "{0:1$}case {2}:", "", indent, label)?,
+            None => write!(f, "{0:1$}default:", "", indent)?,
+        }
+        for statement in &self.body {
+            write!(f, "\n{0:.1$}", statement, indent + 4)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum LValue {
+    Identifier(String),
+    Member(Box<Expression>, String),
+    Index(Box<Expression>, Accessor, Vec<Expression>),
+}
+
+impl LValue {
+    /// The expression which reads the value from this location
+    pub fn to_expression(&self) -> Expression {
+        match self {
+            LValue::Identifier(ref name) => Expression::Identifier(name.clone()),
+            LValue::Member(ref object, ref name) => Expression::Member(object.clone(), name.clone()),
+            LValue::Index(ref array, ref accessor, ref indices) => Expression::Index(array.clone(), *accessor, indices.clone()),
+        }
+    }
+}
+
+impl Display for LValue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // locations are written exactly the same way as they are read
+        self.to_expression().fmt(f)
+    }
+}
+
+/// The kind of data structure being indexed by square brackets
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Accessor {
+    Array,
+    ArrayRef,
+    Map,
+    List,
+    Grid,
+}
+
+impl Display for Accessor {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Accessor::Array => Ok(()),
+            Accessor::ArrayRef => write!(f, "@ "),
+            Accessor::Map => write!(f, "? "),
+            Accessor::List => write!(f, "| "),
+            Accessor::Grid => write!(f, "# "),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Expression {
+    Binary(Operator, Box<Expression>, Box<Expression>),
+    Unary(UnaryOperator, Box<Expression>),
+    Ternary(Box<Expression>, Box<Expression>, Box<Expression>),
+    Call(String, Vec<Expression>),
+    Member(Box<Expression>, String),
+    Index(Box<Expression>, Accessor, Vec<Expression>),
+    PreIncrement(LValue),
+    PreDecrement(LValue),
+    PostIncrement(LValue),
+    PostDecrement(LValue),
+    Identifier(String),
+    Literal(Literal),
+}
+
+impl Expression {
+    /// Shorthand for building a binary operation
+    pub fn binary(op: Operator, lhs: Expression, rhs: Expression) -> Self {
+        Expression::Binary(op, box lhs, box rhs)
+    }
+
+    /// How tightly this expression binds when printed, used to decide where parentheses are needed
+    fn precedence(&self) -> u8 {
+        match self {
+            Expression::Ternary(..) => 0,
+            Expression::Binary(op, ..) => op.precedence(),
+            Expression::Unary(..) | Expression::PreIncrement(..) | Expression::PreDecrement(..) => 8,
+            _ => 9,
+        }
+    }
+
+    /// Writes an operand of this expression, parenthesizing it if it would otherwise bind incorrectly
+    fn fmt_operand(&self, f: &mut Formatter, operand: &Expression, right: bool) -> fmt::Result {
+        let (outer, inner) = (self.precedence(), operand.precedence());
+        let parenthesize = inner < outer
+            || inner == outer && right
+            || inner == outer && match (self, operand) {
+                // GML does not agree with everyone else on the bitwise operators, so don't mix them
+                (Expression::Binary(lop, ..), Expression::Binary(rop, ..)) => outer == 4 && lop != rop,
+                _ => false,
+            };
+        if parenthesize {
+            write!(f, "({})", operand)
+        } else {
+            write!(f, "{}", operand)
+        }
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Expression::Binary(ref op, ref lhs, ref rhs) => {
+                self.fmt_operand(f, lhs, false)?;
+                write!(f, " {} ", op)?;
+                self.fmt_operand(f, rhs, true)
+            }
+            Expression::Unary(ref op, ref operand) => {
+                write!(f, "{}", op)?;
+                self.fmt_operand(f, operand, true)
+            }
+            Expression::Ternary(ref cond, ref first, ref second) => {
+                self.fmt_operand(f, cond, true)?;
+                write!(f, " ? ")?;
+                self.fmt_operand(f, first, true)?;
+                write!(f, " : ")?;
+                self.fmt_operand(f, second, true)
+            }
+            Expression::Call(ref name, ref arguments) => {
+                write!(f, "{}(", name)?;
+                fmt_list(f, arguments)?;
+                write!(f, ")")
+            }
+            Expression::Member(ref object, ref name) => {
+                self.fmt_operand(f, object, false)?;
+                write!(f, ".{}", name)
+            }
+            Expression::Index(ref array, ref accessor, ref indices) => {
+                self.fmt_operand(f, array, false)?;
+                write!(f, "[{}", accessor)?;
+                fmt_list(f, indices)?;
+                write!(f, "]")
+            }
+            Expression::PreIncrement(ref lvalue) => write!(f, "++{}", lvalue),
+            Expression::PreDecrement(ref lvalue) => write!(f, "--{}", lvalue),
+            Expression::PostIncrement(ref lvalue) => write!(f, "{}++", lvalue),
+            Expression::PostDecrement(ref lvalue) => write!(f, "{}--", lvalue),
+            Expression::Identifier(ref name) => write!(f, "{}", name),
+            Expression::Literal(ref literal) => write!(f, "{}", literal),
+        }
+    }
+}
+
+/// Writes a comma separated list of expressions
+fn fmt_list(f: &mut Formatter, items: &Vec<Expression>) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i != 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operator {
+    Plus,
+    Minus,
+    Times,
+    Slash,
+    Pct,
+    Div,
+    Mod,
+    LShift,
+    RShift,
+    And,
+    Or,
+    Xor,
+    BAnd,
+    BOr,
+    Eq,
+    Neq,
+    Lt,
+    Gt,
+    Leq,
+    Geq,
+}
+
+impl Operator {
+    fn precedence(&self) -> u8 {
+        use self::Operator::*;
+        match self {
+            BOr => 1,
+            BAnd => 2,
+            Eq | Neq | Lt | Gt | Leq | Geq => 3,
+            And | Or | Xor => 4,
+            LShift | RShift => 5,
+            Plus | Minus => 6,
+            Times | Slash | Pct | Div | Mod => 7,
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use self::Operator::*;
+        let symbol = match self {
+            Plus => "+",
+            Minus => "-",
+            Times => "*",
+            Slash => "/",
+            Pct => "%",
+            Div => "div",
+            Mod => "mod",
+            LShift => "<<",
+            RShift => ">>",
+            And => "&",
+            Or => "|",
+            Xor => "^",
+            BAnd => "&&",
+            BOr => "||",
+            Eq => "==",
+            Neq => "!=",
+            Lt => "<",
+            Gt => ">",
+            Leq => "<=",
+            Geq => ">=",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnaryOperator {
+    Negative,
+    Not,
+    Inverted,
+}
+
+impl Display for UnaryOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            UnaryOperator::Negative => write!(f, "-"),
+            UnaryOperator::Not => write!(f, "!"),
+            UnaryOperator::Inverted => write!(f, "~"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Literal {
+    Number(f64),
+    Boolean(bool),
+    /// A string literal, exactly as it was written in the source including the quotes
+    String(String),
+    Undefined,
+}
+
+impl Display for Literal {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Literal::Number(n) => n.fmt(f),
+            Literal::Boolean(b) => b.fmt(f),
+            Literal::String(s) => write!(f, "{}", s),
+            Literal::Undefined => write!(f, "undefined"),
+        }
+    }
+}
@@ -1,2 +1,32 @@
+use gmlpp;
+use error::{Error, Warning};
+use symbols::Symbols;
+
 mod ast;
 mod rewriter;
+
+use self::ast::Code;
+use self::rewriter::{Rewrite, Rewriter};
+
+/// The abstract syntax tree of a .gml program
+#[derive(Debug)]
+pub struct AST(Code, Vec<Warning>);
+
+impl AST {
+    /// Lowers a GML++ syntax tree into the equivalent GML
+    pub fn from_gmlpp(ast: &gmlpp::AST, symbols: &Symbols) -> Result<Self, Error> {
+        let mut rewriter = Rewriter::new(symbols);
+        let code = ast.code().rewrite(&mut rewriter)?;
+        Ok(AST(code, rewriter.warnings()))
+    }
+
+    /// The warnings produced while lowering this tree
+    pub fn warnings(&self) -> &[Warning] {
+        &self.1
+    }
+
+    /// Prints the GML code this tree is encoding
+    pub fn print(&self) -> String {
+        format!("{}", self.0)
+    }
+}
@@ -0,0 +1,176 @@
+use gmlpp::ast::{ArgumentList, Type};
+use error::Error;
+use super::super::ast::{Statement, Simple, Expression, LValue, Literal, Operator, Accessor};
+use super::{Rewrite, Rewriter};
+
+fn number(n: usize) -> Expression {
+    Expression::Literal(Literal::Number(n as f64))
+}
+
+fn argument_count() -> Expression {
+    Expression::Identifier("argument_count".to_owned())
+}
+
+/// An access of the `n`th element of the builtin `argument` array
+fn argument(n: Expression) -> Expression {
+    Expression::Index(box Expression::Identifier("argument".to_owned()), Accessor::Array, vec![n])
+}
+
+/// Declares an argument which takes on the default value if it was not supplied by the caller. The
+/// statements hoisted out of the default value only run when it is used
+///
+/// ```gml
+/// var x;
+/// if (argument_count > n)
+///     x = argument[n];
+/// else {
+///     hoisted;
+///     x = default;
+/// }
+/// ```
+fn defaulted(name: String, n: usize, mut hoisted: Vec<Statement>, default: Expression) -> Vec<Statement> {
+    hoisted.push(Statement::Assignment(LValue::Identifier(name.clone()), default));
+    vec![
+        Statement::VarDecl(name.clone(), None),
+        Statement::If(
+            Expression::binary(Operator::Gt, argument_count(), number(n)),
+            box Statement::Assignment(LValue::Identifier(name), argument(number(n))),
+            Some(box Statement::from_vec(hoisted)),
+        ),
+    ]
+}
+
+/// Collects all the arguments from the `n`th onwards into an array
+///
+/// ```gml
+/// var rest = array_create(max(argument_count - n, 0));
+/// for (var i = n; i < argument_count; i = i + 1)
+///     rest[i - n] = argument[i];
+/// ```
+fn variadic(name: String, n: usize, rewriter: &mut Rewriter) -> Vec<Statement> {
+    let i = rewriter.temporary();
+    let index = || Expression::Identifier(i.clone());
+    let length = Expression::Call(
+        "max".to_owned(),
+        vec![Expression::binary(Operator::Minus, argument_count(), number(n)), number(0)],
+    );
+    vec![
+        Statement::VarDecl(name.clone(), Some(Expression::Call("array_create".to_owned(), vec![length]))),
+        Statement::For(
+            Simple::VarDecl(i.clone(), Some(number(n))),
+            Expression::binary(Operator::Lt, index(), argument_count()),
+            Simple::Assignment(LValue::Identifier(i.clone()), Expression::binary(Operator::Plus, index(), number(1))),
+            box Statement::Assignment(
+                LValue::Index(box Expression::Identifier(name), Accessor::Array, vec![Expression::binary(Operator::Minus, index(), number(n))]),
+                argument(index()),
+            ),
+        ),
+    ]
+}
+
+impl Rewrite for ArgumentList {
+    type Output = Vec<Statement>;
+    fn rewrite(&self, rewriter: &mut Rewriter) -> Result<Vec<Statement>, Error> {
+        let mut prologue = vec![];
+        let mut list = self;
+        let mut n = 0;
+        loop {
+            match list {
+                ArgumentList::Argument(ref ident, ref ty, ref rest) => {
+                    rewriter.declare_var(&ident.0, *ty, None);
+                    let name = ident.rewrite(rewriter)?;
+                    prologue.push(Statement::VarDecl(name, Some(argument(number(n)))));
+                    list = rest;
+                }
+                ArgumentList::DefaultArgument(ref ident, ref ty, ref default, ref rest) => {
+                    rewriter.declare_var(&ident.0, *ty, None);
+                    let name = ident.rewrite(rewriter)?;
+                    let (hoisted, default) = rewriter.isolated(|rewriter| default.rewrite(rewriter))?;
+                    prologue.extend(defaulted(name, n, hoisted, default));
+                    list = rest;
+                }
+                ArgumentList::OptionalArgument(ref ident, ref ty, ref rest) => {
+                    rewriter.declare_var(&ident.0, *ty, None);
+                    let name = ident.rewrite(rewriter)?;
+                    prologue.extend(defaulted(name, n, vec![], Expression::Literal(Literal::Undefined)));
+                    list = rest;
+                }
+                ArgumentList::VariadicArgument(ref ident, _) => {
+                    // the arguments are always collected into an array, whatever their type
+                    rewriter.declare_var(&ident.0, Some(Type::Array), None);
+                    let name = ident.rewrite(rewriter)?;
+                    prologue.extend(variadic(name, n, rewriter));
+                    return Ok(prologue);
+                }
+                ArgumentList::End => return Ok(prologue),
+            }
+            n += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::lowered;
+
+    #[test]
+    fn arguments_are_read_from_the_argument_array() {
+        assert_eq!(
+            lowered("argument a\nargument b = 2\nargument c?\nreturn a + b\n").unwrap(),
+            concat!(
+                "var a = argument[0];\n",
+                "var b;\n",
+                "if (argument_count > 1)\n",
+                "    b = argument[1];\n",
+                "else\n",
+                "    b = 2;\n",
+                "var c;\n",
+                "if (argument_count > 2)\n",
+                "    c = argument[2];\n",
+                "else\n",
+                "    c = undefined;\n",
+                "return a + b;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn rest_arguments_are_collected_into_an_array() {
+        assert_eq!(
+            lowered("argument a\nargument ...rest\nreturn rest\n").unwrap(),
+            concat!(
+                "var a = argument[0];\n",
+                "var rest = array_create(max(argument_count - 1, 0));\n",
+                "for (var _gmlpp_1 = 1; _gmlpp_1 < argument_count; _gmlpp_1 = _gmlpp_1 + 1)\n",
+                "    rest[_gmlpp_1 - 1] = argument[_gmlpp_1];\n",
+                "return rest;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn match_in_a_default_only_runs_when_the_default_is_used() {
+        assert_eq!(
+            lowered("argument a\nargument b = match a { 1 => 2, _ => 3 }\nreturn b\n").unwrap(),
+            concat!(
+                "var a = argument[0];\n",
+                "var b;\n",
+                "if (argument_count > 1)\n",
+                "    b = argument[1];\n",
+                "else {\n",
+                "    var _gmlpp_1;\n",
+                "    switch (a) {\n",
+                "        case 1:\n",
+                "            _gmlpp_1 = 2;\n",
+                "            break;\n",
+                "        default:\n",
+                "            _gmlpp_1 = 3;\n",
+                "            break;\n",
+                "    }\n",
+                "    b = _gmlpp_1;\n",
+                "}\n",
+                "return b;\n",
+            ),
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use gmlpp::ast::Assignment;
+use error::Error;
+use super::super::ast::{Statement, Expression, LValue, Operator};
+use super::{Rewrite, Rewriter};
+use super::lvalue::target;
+use super::expression::once;
+
+/// Makes an lvalue safe to both read and write, by evaluating the object and indices it refers to
+/// only once
+fn stable(lvalue: LValue, rewriter: &mut Rewriter) -> LValue {
+    match lvalue {
+        LValue::Identifier(..) => lvalue,
+        LValue::Member(object, name) => LValue::Member(box once(*object, rewriter), name),
+        LValue::Index(array, accessor, indices) => {
+            // a variable holding an array is pure so is left alone, which matters as arrays are copied
+            // when written to through another variable
+            let array = box once(*array, rewriter);
+            let indices = indices.into_iter().map(|index| once(index, rewriter)).collect();
+            LValue::Index(array, accessor, indices)
+        }
+    }
+}
+
+impl Rewrite for Assignment {
+    type Output = Statement;
+    fn rewrite(&self, rewriter: &mut Rewriter) -> Result<Statement, Error> {
+        use gmlpp::ast::Assignment::*;
+        let (lvalue, op, expr) = match self {
+            Eq(ref lvalue, ref expr) => return Ok(Statement::Assignment(target(lvalue, rewriter)?, expr.rewrite(rewriter)?)),
+            Exp(ref lvalue, ref expr) => {
+                let lvalue = stable(target(lvalue, rewriter)?, rewriter);
+                let power = Expression::Call("power".to_owned(), vec![lvalue.to_expression(), expr.rewrite(rewriter)?]);
+                return Ok(Statement::Assignment(lvalue, power));
+            }
+            Plus(ref lvalue, ref expr) => (lvalue, Operator::Plus, expr),
+            Minus(ref lvalue, ref expr) => (lvalue, Operator::Minus, expr),
+            Times(ref lvalue, ref expr) => (lvalue, Operator::Times, expr),
+            Slash(ref lvalue, ref expr) => (lvalue, Operator::Slash, expr),
+            Pct(ref lvalue, ref expr) => (lvalue, Operator::Pct, expr),
+            And(ref lvalue, ref expr) => (lvalue, Operator::And, expr),
+            Or(ref lvalue, ref expr) => (lvalue, Operator::Or, expr),
+            Xor(ref lvalue, ref expr) => (lvalue, Operator::Xor, expr),
+            LShift(ref lvalue, ref expr) => (lvalue, Operator::LShift, expr),
+            RShift(ref lvalue, ref expr) => (lvalue, Operator::RShift, expr),
+        };
+        let lvalue = target(lvalue, rewriter)?;
+        match op {
+            Operator::Plus | Operator::Minus | Operator::Times | Operator::Slash | Operator::And | Operator::Or | Operator::Xor =>
+                Ok(Statement::CompoundAssignment(lvalue, op, expr.rewrite(rewriter)?)),
+            // the rest have no compound assignment in GML, so are written out in full
+            _ => {
+                let lvalue = stable(lvalue, rewriter);
+                let value = Expression::binary(op, lvalue.to_expression(), expr.rewrite(rewriter)?);
+                Ok(Statement::Assignment(lvalue, value))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::lowered;
+
+    #[test]
+    fn exponent_assignment_is_written_out() {
+        assert_eq!(lowered("x **= y\n").unwrap(), "x = power(x, y);\n");
+    }
+
+    #[test]
+    fn compound_assignments_gml_has_are_kept() {
+        assert_eq!(lowered("x += 1\nx -= 2\nx *= 3\nx /= 4\nx &= 5\nx |= 6\nx ^= 7\n").unwrap(), "x += 1;\nx -= 2;\nx *= 3;\nx /= 4;\nx &= 5;\nx |= 6;\nx ^= 7;\n");
+    }
+
+    #[test]
+    fn compound_assignments_gml_lacks_are_written_out() {
+        assert_eq!(lowered("x %= 2\nx <<= 3\nx >>= 4\n").unwrap(), "x = x % 2;\nx = x << 3;\nx = x >> 4;\n");
+    }
+
+    #[test]
+    fn increments_and_decrements_are_kept() {
+        assert_eq!(lowered("x++\n--y\nz = w++\n").unwrap(), "x++;\n--y;\nz = w++;\n");
+    }
+
+    #[test]
+    fn written_out_assignment_evaluates_its_target_once() {
+        assert_eq!(lowered("a[| f()].b <<= 1\n").unwrap(), "var _gmlpp_1 = a[| f()];\n_gmlpp_1.b = _gmlpp_1.b << 1;\n");
+    }
+
+    #[test]
+    fn array_which_is_not_a_variable_is_evaluated_once() {
+        assert_eq!(
+            lowered("l[| g()][0] %= 2\nxs[i] %= 2\n").unwrap(),
+            concat!(
+                "var _gmlpp_1 = l[| g()];\n",
+                "_gmlpp_1[0] = _gmlpp_1[0] % 2;\n",
+                "xs[i] = xs[i] % 2;\n",
+            ),
+        );
+    }
+}
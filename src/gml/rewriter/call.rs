@@ -0,0 +1,125 @@
+use gmlpp::ast::{self, Call, CommaList, Value};
+use error::{Error, RewriteError};
+use super::super::ast::Expression;
+use super::{Rewrite, Rewriter};
+use super::expression::{sequence, once, is_pure};
+
+impl Rewrite for CommaList {
+    type Output = Vec<Expression>;
+    fn rewrite(&self, rewriter: &mut Rewriter) -> Result<Vec<Expression>, Error> {
+        sequence(vec![], &self.0, rewriter)
+    }
+}
+
+impl Rewrite for Call {
+    type Output = Expression;
+    fn rewrite(&self, rewriter: &mut Rewriter) -> Result<Expression, Error> {
+        Ok(Expression::Call(self.name.rewrite(rewriter)?, self.arguments.rewrite(rewriter)?))
+    }
+}
+
+/// Rewrites the right hand side of a pipe, passing the piped value in place of the placeholder
+/// argument, or as the first argument if there is no placeholder. The piped value is evaluated
+/// before the other arguments
+pub fn piped(call: &Call, value: Expression, rewriter: &mut Rewriter) -> Result<Expression, Error> {
+    let name = call.name.rewrite(rewriter)?;
+    let mut placeholders = call.arguments.0
+        .iter()
+        .enumerate()
+        .filter(|&(_, argument)| is_placeholder(argument))
+        .map(|(index, _)| index);
+    let position = placeholders.next().unwrap_or(0);
+    if placeholders.next().is_some() {
+        return Err(Error::RewriteError(RewriteError::MultiplePlaceholders).at(call.span));
+    }
+    let rest = call.arguments.0.iter().filter(|argument| !is_placeholder(argument));
+    let mut arguments = sequence(vec![value], rest, rewriter)?;
+    let mut value = arguments.remove(0);
+    // arguments before the placeholder come first in the call, so the value is stored before them
+    // if moving it after them could change what happens
+    if arguments[..position].iter().any(|argument| !is_pure(argument)) {
+        value = once(value, rewriter);
+    }
+    arguments.insert(position, value);
+    Ok(Expression::Call(name, arguments))
+}
+
+/// Whether an argument is where the piped value goes
+fn is_placeholder(argument: &ast::Expression) -> bool {
+    match argument {
+        ast::Expression::Value(Value::Placeholder, _) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::lowered;
+
+    #[test]
+    fn pipe_passes_the_value_as_the_first_argument() {
+        assert_eq!(lowered("x = a |> f() |> g(1)\n").unwrap(), "x = g(f(a), 1);\n");
+    }
+
+    #[test]
+    fn pipe_passes_the_value_in_place_of_the_placeholder() {
+        assert_eq!(lowered("x = a |> f(1, _)\n").unwrap(), "x = f(1, a);\n");
+    }
+
+    #[test]
+    fn pipe_with_more_than_one_placeholder_is_rejected() {
+        let error = lowered("x = a |> f(_, _)\n").unwrap_err();
+        assert_eq!(error.code(), "multiple-placeholders");
+        assert_eq!(error.span().map(|span| span.start.column), Some(10));
+    }
+
+    #[test]
+    fn match_in_a_later_argument_is_evaluated_after_the_earlier_ones() {
+        assert_eq!(
+            lowered("g(f(), match y { 1 => 2, _ => 3 })\n").unwrap(),
+            concat!(
+                "var _gmlpp_2 = f();\n",
+                "var _gmlpp_1;\n",
+                "switch (y) {\n",
+                "    case 1:\n",
+                "        _gmlpp_1 = 2;\n",
+                "        break;\n",
+                "    default:\n",
+                "        _gmlpp_1 = 3;\n",
+                "        break;\n",
+                "}\n",
+                "g(_gmlpp_2, _gmlpp_1);\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn match_in_a_piped_call_is_evaluated_after_the_piped_value() {
+        assert_eq!(
+            lowered("x = f() |> g(match y { 1 => 2, _ => 3 })\n").unwrap(),
+            concat!(
+                "var _gmlpp_2 = f();\n",
+                "var _gmlpp_1;\n",
+                "switch (y) {\n",
+                "    case 1:\n",
+                "        _gmlpp_1 = 2;\n",
+                "        break;\n",
+                "    default:\n",
+                "        _gmlpp_1 = 3;\n",
+                "        break;\n",
+                "}\n",
+                "x = g(_gmlpp_2, _gmlpp_1);\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn value_piped_after_other_arguments_is_evaluated_first() {
+        assert_eq!(lowered("x = a() |> f(b(), _)\n").unwrap(), "var _gmlpp_1 = a();\nx = f(b(), _gmlpp_1);\n");
+    }
+
+    #[test]
+    fn value_piped_after_pure_arguments_is_left_in_place() {
+        assert_eq!(lowered("x = a() |> f(1, y, _)\n").unwrap(), "x = f(1, y, a());\n");
+    }
+}
@@ -0,0 +1,65 @@
+use gmlpp::ast;
+use error::Error;
+use super::super::ast::Case;
+use super::{Rewrite, Rewriter};
+
+impl Rewrite for ast::Case {
+    type Output = Case;
+    fn rewrite(&self, rewriter: &mut Rewriter) -> Result<Case, Error> {
+        let label = match self.label {
+            Some(ref label) => Some(label.rewrite(rewriter)?),
+            None => None,
+        };
+        let mut body = vec![];
+        for statement in &self.body {
+            body.extend(statement.rewrite(rewriter)?);
+        }
+        Ok(Case { label, body })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gmlpp;
+    use gml::AST;
+    use symbols::Symbols;
+    use super::super::lowered;
+
+    /// The number of warnings lowering a source file produces
+    fn warnings(source: &str) -> usize {
+        let ast = gmlpp::AST::from_reader(source.as_bytes()).unwrap();
+        AST::from_gmlpp(&ast, &Symbols::new()).unwrap().warnings().len()
+    }
+
+    #[test]
+    fn switch_keeps_its_cases() {
+        assert_eq!(
+            lowered("switch (x) {\n    case 1:\n    case 2:\n        a()\n        break\n    default:\n        b()\n}\n").unwrap(),
+            concat!(
+                "switch (x) {\n",
+                "    case 1:\n",
+                "    case 2:\n",
+                "        a();\n",
+                "        break;\n",
+                "    default:\n",
+                "        b();\n",
+                "}\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn case_which_falls_through_is_warned_about() {
+        assert_eq!(warnings("switch (x) {\n    case 1:\n        a()\n    case 2:\n        b()\n}\n"), 1);
+    }
+
+    #[test]
+    fn case_which_is_marked_as_falling_through_is_not_warned_about() {
+        assert_eq!(warnings("switch (x) {\n    case 1:\n        a()\n        // fallthrough\n    case 2:\n        b()\n}\n"), 0);
+    }
+
+    #[test]
+    fn case_which_cannot_reach_the_next_is_not_warned_about() {
+        assert_eq!(warnings("switch (x) {\n    case 1:\n        if (y) {\n            return 1\n        } else {\n            break\n        }\n    case 2:\n        b()\n}\n"), 0);
+    }
+}
@@ -0,0 +1,16 @@
+use gmlpp::ast;
+use error::Error;
+use super::super::ast::Code;
+use super::{Rewrite, Rewriter};
+
+impl Rewrite for ast::Code {
+    type Output = Code;
+    fn rewrite(&self, rewriter: &mut Rewriter) -> Result<Code, Error> {
+        let docs = self.docs.0.clone();
+        let mut body = self.args.rewrite(rewriter)?;
+        for statement in &self.body.0 {
+            body.extend(statement.rewrite(rewriter)?);
+        }
+        Ok(Code { docs, body })
+    }
+}
@@ -0,0 +1,172 @@
+use gmlpp::ast;
+use error::{Error, RewriteError};
+use super::super::ast::{Statement, Expression, Operator};
+use super::{Rewrite, Rewriter};
+use super::call::piped;
+
+impl Rewrite for ast::Expression {
+    type Output = Expression;
+    fn rewrite(&self, rewriter: &mut Rewriter) -> Result<Expression, Error> {
+        lower(self, rewriter).map_err(|error| error.at(self.span()))
+    }
+}
+
+/// Lowers an expression to GML. Any error it raises is located at the expression, unless it was
+/// raised by some smaller part of it
+fn lower(expr: &ast::Expression, rewriter: &mut Rewriter) -> Result<Expression, Error> {
+    use gmlpp::ast::Expression::*;
+    let (op, lhs, rhs) = match expr {
+        Plus(ref lhs, ref rhs) => (Operator::Plus, lhs, rhs),
+        Minus(ref lhs, ref rhs) => (Operator::Minus, lhs, rhs),
+        Times(ref lhs, ref rhs) => (Operator::Times, lhs, rhs),
+        Slash(ref lhs, ref rhs) => (Operator::Slash, lhs, rhs),
+        Pct(ref lhs, ref rhs) => (Operator::Pct, lhs, rhs),
+        Mod(ref lhs, ref rhs) => (Operator::Mod, lhs, rhs),
+        Div(ref lhs, ref rhs) => (Operator::Div, lhs, rhs),
+        LShift(ref lhs, ref rhs) => (Operator::LShift, lhs, rhs),
+        RShift(ref lhs, ref rhs) => (Operator::RShift, lhs, rhs),
+        And(ref lhs, ref rhs) => (Operator::And, lhs, rhs),
+        Or(ref lhs, ref rhs) => (Operator::Or, lhs, rhs),
+        Xor(ref lhs, ref rhs) => (Operator::Xor, lhs, rhs),
+        // the right hand side of a logical operator is skipped when the left decides the result
+        BAnd(ref lhs, ref rhs) => return Ok(Expression::binary(Operator::BAnd, lhs.rewrite(rewriter)?, conditional(rhs, rewriter)?)),
+        BOr(ref lhs, ref rhs) => return Ok(Expression::binary(Operator::BOr, lhs.rewrite(rewriter)?, conditional(rhs, rewriter)?)),
+        Eq(ref lhs, ref rhs) => (Operator::Eq, lhs, rhs),
+        Neq(ref lhs, ref rhs) => (Operator::Neq, lhs, rhs),
+        Lt(ref lhs, ref rhs) => (Operator::Lt, lhs, rhs),
+        Gt(ref lhs, ref rhs) => (Operator::Gt, lhs, rhs),
+        Leq(ref lhs, ref rhs) => (Operator::Leq, lhs, rhs),
+        Geq(ref lhs, ref rhs) => (Operator::Geq, lhs, rhs),
+        // GML has no exponent operator, only the function
+        Exp(ref lhs, ref rhs) => return Ok(Expression::Call("power".to_owned(), sequence(vec![], vec![&**lhs, &**rhs], rewriter)?)),
+        Pipe(ref lhs, ref call) => {
+            let value = lhs.rewrite(rewriter)?;
+            return piped(call, value, rewriter);
+        }
+        Ternary(ref cond, ref first, ref second) =>
+            return Ok(Expression::Ternary(
+                box cond.rewrite(rewriter)?,
+                box conditional(first, rewriter)?,
+                box conditional(second, rewriter)?,
+            )),
+        Value(ref value, _) => return value.rewrite(rewriter),
+    };
+    let mut operands = sequence(vec![], vec![&**lhs, &**rhs], rewriter)?;
+    let rhs = operands.pop().unwrap();
+    let lhs = operands.pop().unwrap();
+    Ok(Expression::binary(op, lhs, rhs))
+}
+
+/// Whether evaluating an expression has no side effects, so it can be repeated or moved without
+/// changing what the code does
+pub fn is_pure(expr: &Expression) -> bool {
+    match expr {
+        Expression::Identifier(..) | Expression::Literal(..) => true,
+        Expression::Member(ref object, _) => is_pure(object),
+        Expression::Index(ref array, _, ref indices) => is_pure(array) && indices.iter().all(is_pure),
+        Expression::Unary(_, ref inner) => is_pure(inner),
+        Expression::Binary(_, ref lhs, ref rhs) => is_pure(lhs) && is_pure(rhs),
+        Expression::Ternary(ref cond, ref first, ref second) => is_pure(cond) && is_pure(first) && is_pure(second),
+        _ => false,
+    }
+}
+
+/// Stores an expression in a temporary variable before the current statement, unless it is pure
+pub fn once(expr: Expression, rewriter: &mut Rewriter) -> Expression {
+    if is_pure(&expr) {
+        return expr;
+    }
+    let temporary = rewriter.temporary();
+    rewriter.hoist(vec![Statement::VarDecl(temporary.clone(), Some(expr))]);
+    Expression::Identifier(temporary)
+}
+
+/// Lowers operands which are evaluated in order, following some which have already been lowered. When
+/// one of them hoists statements out, the operands before it are stored in temporaries first, so they
+/// are still evaluated before it
+pub fn sequence<'e, I>(mut lowered: Vec<Expression>, operands: I, rewriter: &mut Rewriter) -> Result<Vec<Expression>, Error>
+where I: IntoIterator<Item = &'e ast::Expression> {
+    // the operands before this one are already stored, so are not stored again
+    let mut stored = 0;
+    for operand in operands {
+        let (hoisted, operand) = rewriter.isolated(|rewriter| operand.rewrite(rewriter))?;
+        if !hoisted.is_empty() {
+            let earlier = lowered.split_off(stored);
+            lowered.extend(earlier.into_iter().map(|earlier| once(earlier, rewriter)));
+            stored = lowered.len();
+            rewriter.hoist(hoisted);
+        }
+        lowered.push(operand);
+    }
+    Ok(lowered)
+}
+
+/// Lowers an operand which is not always evaluated, so cannot have anything hoisted out of it to run
+/// before the statement it is in
+pub fn conditional(operand: &ast::Expression, rewriter: &mut Rewriter) -> Result<Expression, Error> {
+    match rewriter.isolated(|rewriter| operand.rewrite(rewriter))? {
+        (ref hoisted, operand) if hoisted.is_empty() => Ok(operand),
+        _ => Err(Error::RewriteError(RewriteError::HoistInConditionalExpression).at(operand.span())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::lowered;
+
+    #[test]
+    fn exponent_is_lowered_to_power() {
+        assert_eq!(lowered("x = a ** b ** 2 + 1\n").unwrap(), "x = power(a, power(b, 2)) + 1;\n");
+    }
+
+    #[test]
+    fn match_after_an_operand_is_evaluated_after_it() {
+        assert_eq!(
+            lowered("x = f() + match y { 1 => 2, _ => 3 }\n").unwrap(),
+            concat!(
+                "var _gmlpp_2 = f();\n",
+                "var _gmlpp_1;\n",
+                "switch (y) {\n",
+                "    case 1:\n",
+                "        _gmlpp_1 = 2;\n",
+                "        break;\n",
+                "    default:\n",
+                "        _gmlpp_1 = 3;\n",
+                "        break;\n",
+                "}\n",
+                "x = _gmlpp_2 + _gmlpp_1;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn match_after_a_pure_operand_leaves_it_in_place() {
+        assert_eq!(
+            lowered("x = z + match y { 1 => 2, _ => 3 }\n").unwrap(),
+            concat!(
+                "var _gmlpp_1;\n",
+                "switch (y) {\n",
+                "    case 1:\n",
+                "        _gmlpp_1 = 2;\n",
+                "        break;\n",
+                "    default:\n",
+                "        _gmlpp_1 = 3;\n",
+                "        break;\n",
+                "}\n",
+                "x = z + _gmlpp_1;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn match_in_the_rhs_of_a_logical_operator_is_rejected() {
+        let error = lowered("x = f() && match y { 1 => true, _ => false }\n").unwrap_err();
+        assert_eq!(error.code(), "hoist-in-conditional-expression");
+    }
+
+    #[test]
+    fn stored_piped_value_in_the_rhs_of_a_logical_operator_is_rejected() {
+        let error = lowered("x = ok && (a() |> f(b(), _))\n").unwrap_err();
+        assert_eq!(error.code(), "hoist-in-conditional-expression");
+    }
+}
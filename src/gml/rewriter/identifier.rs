@@ -0,0 +1,10 @@
+use gmlpp::ast::Identifier;
+use error::Error;
+use super::{Rewrite, Rewriter};
+
+impl Rewrite for Identifier {
+    type Output = String;
+    fn rewrite(&self, _: &mut Rewriter) -> Result<String, Error> {
+        Ok(self.0.clone())
+    }
+}
@@ -0,0 +1,227 @@
+//! Lowers `for ... in` and `for ... of` loops into plain GML `for` loops
+
+use gmlpp::ast::{self, Identifier};
+use error::Error;
+use super::super::ast::{Statement, Simple, Expression, LValue, Literal, Operator, UnaryOperator, Accessor};
+use super::scope::Collection;
+use super::expression::{sequence, once, is_pure};
+use super::{Rewrite, Rewriter};
+
+fn call(name: &str, arguments: Vec<Expression>) -> Expression {
+    Expression::Call(name.to_owned(), arguments)
+}
+
+/// The kind of collection an expression evaluates to, if it is a variable and what it holds is known
+fn collection(expr: &ast::Expression, rewriter: &Rewriter) -> Option<Collection> {
+    if let ast::Expression::Value(ast::Value::LValue(ast::LValue::Identifier(ref ident)), _) = expr {
+        return rewriter.resolve(&ident.0).and_then(|binding| binding.collection);
+    }
+    None
+}
+
+/// Rewrites the body of a loop, with some statements to run at the start of every iteration
+fn body(mut prologue: Vec<Statement>, statement: &ast::Statement, rewriter: &mut Rewriter) -> Result<Box<Statement>, Error> {
+    let mut statements = statement.rewrite(rewriter)?;
+    match statements.pop() {
+        Some(Statement::Block(block)) if statements.is_empty() => prologue.extend(block),
+        last => {
+            prologue.extend(statements);
+            prologue.extend(last);
+        }
+    }
+    Ok(box Statement::from_vec(prologue))
+}
+
+/// Counts up from the start to just before the end
+///
+/// ```gml
+/// for (var i = start; i < end; i++)
+/// ```
+fn counting(index: String, start: Expression, end: Expression, body: Box<Statement>) -> Statement {
+    Statement::For(
+        Simple::VarDecl(index.clone(), Some(start)),
+        Expression::binary(Operator::Lt, Expression::Identifier(index.clone()), end),
+        Simple::Expression(Expression::PostIncrement(LValue::Identifier(index))),
+        body,
+    )
+}
+
+/// Visits every key of a map
+///
+/// ```gml
+/// for (var k = ds_map_find_first(map); !is_undefined(k); k = ds_map_find_next(map, k))
+/// ```
+fn keys(key: String, map: Expression, body: Box<Statement>) -> Statement {
+    let current = || Expression::Identifier(key.clone());
+    Statement::For(
+        Simple::VarDecl(key.clone(), Some(call("ds_map_find_first", vec![map.clone()]))),
+        Expression::Unary(UnaryOperator::Not, box call("is_undefined", vec![current()])),
+        Simple::Assignment(LValue::Identifier(key.clone()), call("ds_map_find_next", vec![map, current()])),
+        body,
+    )
+}
+
+/// The number of elements in a collection
+fn length(collection: Collection, value: Expression) -> Expression {
+    match collection {
+        Collection::Array => call("array_length_1d", vec![value]),
+        Collection::List => call("ds_list_size", vec![value]),
+        Collection::Map => call("ds_map_size", vec![value]),
+    }
+}
+
+/// Lowers `for (item of collection)`, which visits every value in an array, list or map. Anything
+/// not known to be a list or map is assumed to be an array
+pub fn for_of(ident: &Identifier, expr: &ast::Expression, statement: &ast::Statement, rewriter: &mut Rewriter) -> Result<Vec<Statement>, Error> {
+    let kind = collection(expr, rewriter).unwrap_or(Collection::Array);
+    let value = expr.rewrite(rewriter)?;
+    let value = once(value, rewriter);
+    let lowered = rewriter.scoped(|rewriter| {
+        let name = rewriter.declare(ident, false, None, None)?;
+        let index = rewriter.temporary();
+        let accessor = match kind {
+            Collection::Array => Accessor::Array,
+            Collection::List => Accessor::List,
+            Collection::Map => Accessor::Map,
+        };
+        let element = Expression::Index(box value.clone(), accessor, vec![Expression::Identifier(index.clone())]);
+        let body = body(vec![Statement::VarDecl(name, Some(element))], statement, rewriter)?;
+        match kind {
+            Collection::Map => Ok(keys(index, value.clone(), body)),
+            _ => Ok(counting(index, Expression::Literal(Literal::Number(0.0)), length(kind, value.clone()), body)),
+        }
+    })?;
+    Ok(vec![lowered])
+}
+
+/// Lowers `for (key in collection)`, which visits every key of a map, or every index of an array or
+/// list. Anything not known to be an array or list is assumed to be a map
+pub fn for_in(ident: &Identifier, expr: &ast::Expression, statement: &ast::Statement, rewriter: &mut Rewriter) -> Result<Vec<Statement>, Error> {
+    let kind = collection(expr, rewriter).unwrap_or(Collection::Map);
+    let value = expr.rewrite(rewriter)?;
+    let value = once(value, rewriter);
+    let lowered = rewriter.scoped(|rewriter| {
+        let name = rewriter.declare(ident, false, None, None)?;
+        let body = body(vec![], statement, rewriter)?;
+        match kind {
+            Collection::Map => Ok(keys(name, value.clone(), body)),
+            _ => Ok(counting(name, Expression::Literal(Literal::Number(0.0)), length(kind, value.clone()), body)),
+        }
+    })?;
+    Ok(vec![lowered])
+}
+
+/// Lowers `for (i in start..end)`, which counts from the start up to, but not including, the end
+pub fn for_range(ident: &Identifier, start: &ast::Expression, end: &ast::Expression, statement: &ast::Statement, rewriter: &mut Rewriter) -> Result<Vec<Statement>, Error> {
+    let mut bounds = sequence(vec![], vec![start, end], rewriter)?;
+    let end = bounds.pop().unwrap();
+    let start = bounds.pop().unwrap();
+    // the end is evaluated every iteration, so it is stored first, along with the start so that they
+    // are still evaluated in order
+    let (start, end) = if is_pure(&end) {
+        (start, end)
+    } else {
+        (once(start, rewriter), once(end, rewriter))
+    };
+    let lowered = rewriter.scoped(|rewriter| {
+        let name = rewriter.declare(ident, false, None, None)?;
+        let body = body(vec![], statement, rewriter)?;
+        Ok(counting(name, start, end, body))
+    })?;
+    Ok(vec![lowered])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::lowered;
+
+    #[test]
+    fn for_of_visits_the_values_of_an_array() {
+        assert_eq!(
+            lowered("let xs = array_create(3)\nfor (x of xs) {\n    show_debug_message(x)\n}\n").unwrap(),
+            concat!(
+                "var _gmlpp_1_xs = array_create(3);\n",
+                "for (var _gmlpp_3 = 0; _gmlpp_3 < array_length_1d(_gmlpp_1_xs); _gmlpp_3++) {\n",
+                "    var _gmlpp_2_x = _gmlpp_1_xs[_gmlpp_3];\n",
+                "    show_debug_message(_gmlpp_2_x);\n",
+                "}\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn for_of_visits_the_values_of_a_list() {
+        assert_eq!(
+            lowered("let xs = ds_list_create()\nfor (x of xs) {\n    show_debug_message(x)\n}\n").unwrap(),
+            concat!(
+                "var _gmlpp_1_xs = ds_list_create();\n",
+                "for (var _gmlpp_3 = 0; _gmlpp_3 < ds_list_size(_gmlpp_1_xs); _gmlpp_3++) {\n",
+                "    var _gmlpp_2_x = _gmlpp_1_xs[| _gmlpp_3];\n",
+                "    show_debug_message(_gmlpp_2_x);\n",
+                "}\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn for_in_visits_the_keys_of_a_map() {
+        assert_eq!(
+            lowered("for (k in get_map()) {\n    show_debug_message(k)\n}\n").unwrap(),
+            concat!(
+                "var _gmlpp_1 = get_map();\n",
+                "for (var _gmlpp_2_k = ds_map_find_first(_gmlpp_1); !is_undefined(_gmlpp_2_k); _gmlpp_2_k = ds_map_find_next(_gmlpp_1, _gmlpp_2_k))\n",
+                "    show_debug_message(_gmlpp_2_k);\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn for_in_counts_through_a_range() {
+        assert_eq!(
+            lowered("for (i in 0..count()) {\n    show_debug_message(i)\n}\n").unwrap(),
+            concat!(
+                "var _gmlpp_1 = count();\n",
+                "for (var _gmlpp_2_i = 0; _gmlpp_2_i < _gmlpp_1; _gmlpp_2_i++)\n",
+                "    show_debug_message(_gmlpp_2_i);\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn range_is_evaluated_from_start_to_end() {
+        assert_eq!(
+            lowered("for (i in first()..last()) {\n    show_debug_message(i)\n}\n").unwrap(),
+            concat!(
+                "var _gmlpp_1 = first();\n",
+                "var _gmlpp_2 = last();\n",
+                "for (var _gmlpp_3_i = _gmlpp_1; _gmlpp_3_i < _gmlpp_2; _gmlpp_3_i++)\n",
+                "    show_debug_message(_gmlpp_3_i);\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn range_with_a_pure_end_is_not_stored() {
+        assert_eq!(
+            lowered("for (i in first()..10) {\n    show_debug_message(i)\n}\n").unwrap(),
+            concat!(
+                "for (var _gmlpp_1_i = first(); _gmlpp_1_i < 10; _gmlpp_1_i++)\n",
+                "    show_debug_message(_gmlpp_1_i);\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn for_of_uses_the_declared_type_of_the_collection() {
+        assert_eq!(
+            lowered("let xs: List = make_list()\nfor (x of xs) {\n    show_debug_message(x)\n}\n").unwrap(),
+            concat!(
+                "var _gmlpp_1_xs = make_list();\n",
+                "for (var _gmlpp_3 = 0; _gmlpp_3 < ds_list_size(_gmlpp_1_xs); _gmlpp_3++) {\n",
+                "    var _gmlpp_2_x = _gmlpp_1_xs[| _gmlpp_3];\n",
+                "    show_debug_message(_gmlpp_2_x);\n",
+                "}\n",
+            ),
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use gmlpp::ast;
+use error::Error;
+use super::super::ast::Literal;
+use super::{Rewrite, Rewriter};
+
+impl Rewrite for ast::Literal {
+    type Output = Literal;
+    fn rewrite(&self, _: &mut Rewriter) -> Result<Literal, Error> {
+        match self {
+            ast::Literal::Numeric(n) => Ok(Literal::Number(*n)),
+            ast::Literal::Boolean(b) => Ok(Literal::Boolean(*b)),
+            ast::Literal::String(ref s) => Ok(Literal::String(s.clone())),
+            // GML has no characters, so they are just strings of length one
+            ast::Literal::Char(c) => Ok(Literal::String(format!("{:?}", c.to_string()))),
+            ast::Literal::Undefined => Ok(Literal::Undefined),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use gmlpp::ast;
+use error::{Error, RewriteError};
+use super::super::ast::{LValue, Accessor};
+use super::{Rewrite, Rewriter};
+
+impl Rewrite for ast::Accessor {
+    type Output = Accessor;
+    fn rewrite(&self, _: &mut Rewriter) -> Result<Accessor, Error> {
+        match self {
+            ast::Accessor::Array => Ok(Accessor::Array),
+            ast::Accessor::ArrayRef => Ok(Accessor::ArrayRef),
+            ast::Accessor::Map => Ok(Accessor::Map),
+            ast::Accessor::List => Ok(Accessor::List),
+            ast::Accessor::Grid => Ok(Accessor::Grid),
+        }
+    }
+}
+
+impl Rewrite for ast::LValue {
+    type Output = LValue;
+    fn rewrite(&self, rewriter: &mut Rewriter) -> Result<LValue, Error> {
+        match self {
+            ast::LValue::Identifier(ref ident) =>
+                match rewriter.resolve(&ident.0) {
+                    Some(binding) => Ok(LValue::Identifier(binding.name.clone())),
+                    None => Ok(LValue::Identifier(ident.rewrite(rewriter)?)),
+                },
+            ast::LValue::Global(..) => Ok(LValue::Identifier("global".to_owned())),
+            ast::LValue::Member(ref object, ref ident) => {
+                if let ast::LValue::Identifier(ref name) = **object {
+                    if let Some(members) = rewriter.symbols().enum_members(&name.0) {
+                        if !members.contains(&ident.0) {
+                            return Err(Error::RewriteError(RewriteError::UnknownEnumMember).at(ident.1));
+                        }
+                    }
+                }
+                Ok(LValue::Member(box object.rewrite(rewriter)?.to_expression(), ident.rewrite(rewriter)?))
+            }
+            ast::LValue::Index(ref array, ref accessor, ref indices) =>
+                Ok(LValue::Index(box array.rewrite(rewriter)?.to_expression(), accessor.rewrite(rewriter)?, indices.rewrite(rewriter)?)),
+        }
+    }
+}
+
+/// Rewrites an lvalue which is being assigned to, which cannot be a constant
+pub fn target(lvalue: &ast::LValue, rewriter: &mut Rewriter) -> Result<LValue, Error> {
+    if let ast::LValue::Identifier(ref ident) = lvalue {
+        if rewriter.resolve(&ident.0).map_or(false, |binding| binding.constant) {
+            return Err(Error::RewriteError(RewriteError::AssignmentToConstant).at(ident.1));
+        }
+    }
+    lvalue.rewrite(rewriter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{lowered, lowered_with};
+
+    #[test]
+    fn members_and_indices_can_be_assigned() {
+        assert_eq!(
+            lowered("other.hp = 3\nglobal.score = 0\narr[i] = 1\narr[i, j] = 2\n").unwrap(),
+            concat!(
+                "other.hp = 3;\n",
+                "global.score = 0;\n",
+                "arr[i] = 1;\n",
+                "arr[i, j] = 2;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn accessors_can_be_assigned() {
+        assert_eq!(
+            lowered("arr[@ 0] = v\nmap[? \"k\"] = v\nlist[| 0] = v\ngrid[# x, y] = v\n").unwrap(),
+            concat!(
+                "arr[@ 0] = v;\n",
+                "map[? \"k\"] = v;\n",
+                "list[| 0] = v;\n",
+                "grid[# x, y] = v;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn members_and_indices_can_be_read() {
+        assert_eq!(
+            lowered("x = other.items[| 0].hp + grid[# 1, 2]\n").unwrap(),
+            "x = other.items[| 0].hp + grid[# 1, 2];\n",
+        );
+    }
+
+    #[test]
+    fn members_of_enums_from_other_files_can_be_used() {
+        assert_eq!(lowered_with("enum Dir { Up, Down }\n", "x = Dir.Down\n").unwrap(), "x = Dir.Down;\n");
+    }
+
+    #[test]
+    fn unknown_enum_member_is_rejected() {
+        assert_eq!(lowered_with("enum Dir { Up, Down }\n", "x = Dir.Left\n").unwrap_err().code(), "unknown-enum-member");
+    }
+}
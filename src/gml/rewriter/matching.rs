@@ -0,0 +1,290 @@
+use gmlpp::ast::{self, Arm, Match, Pattern, Value};
+use error::{Error, RewriteError, Warning};
+use super::super::ast::{Statement, Expression, Case, LValue, Operator};
+use super::{Rewrite, Rewriter};
+
+/// The names of the enum and member a pattern refers to, if it is a member of a known enum
+fn enum_member<'p>(pattern: &'p Pattern, rewriter: &Rewriter) -> Option<(&'p str, &'p str)> {
+    if let Pattern::Value(Value::LValue(ast::LValue::Member(ref object, ref member))) = pattern {
+        if let ast::LValue::Identifier(ref name) = **object {
+            if rewriter.symbols().enum_members(&name.0).is_some() {
+                return Some((&name.0, &member.0));
+            }
+        }
+    }
+    None
+}
+
+/// Whether any of the patterns of an arm is a wildcard
+fn has_wildcard(arm: &Arm) -> bool {
+    arm.patterns.iter().any(|pattern| match pattern {
+        Pattern::Wildcard => true,
+        _ => false,
+    })
+}
+
+/// The arms of a match which can be chosen. Everything after the first wildcard is unreachable, and
+/// is left out so that a switch does not try its cases before falling back to the wildcard
+fn reachable(expr: &Match) -> &[Arm] {
+    let end = expr.arms.iter().position(has_wildcard).map_or(expr.arms.len(), |index| index + 1);
+    &expr.arms[..end]
+}
+
+/// Whether a pattern can be used as the label of a switch case
+fn is_constant(pattern: &Pattern, rewriter: &Rewriter) -> bool {
+    match pattern {
+        Pattern::Wildcard | Pattern::Value(Value::Literal(..)) => true,
+        Pattern::Value(Value::Negative(ref inner)) =>
+            match **inner {
+                Value::Literal(..) => true,
+                _ => false,
+            },
+        _ => enum_member(pattern, rewriter).is_some(),
+    }
+}
+
+/// Whether a statement has a `break` which is not inside a loop of its own, which would break out of
+/// the switch rather than the loop the match is in
+fn breaks(statement: &ast::Statement) -> bool {
+    match statement.kind {
+        ast::StatementKind::Break => true,
+        ast::StatementKind::Block(ref statements) => statements.iter().any(breaks),
+        ast::StatementKind::If(_, ref tbody, ref fbody) => breaks(tbody) || fbody.as_ref().map_or(false, |fbody| breaks(fbody)),
+        ast::StatementKind::Match(ref expr) => expr.arms.iter().any(|arm| breaks(&arm.body)),
+        _ => false,
+    }
+}
+
+/// Makes sure a match covers every value it could be given. Only matches on enums can be exhaustive
+/// without a wildcard, and those used as statements are allowed to do nothing for other values
+fn check_exhaustive(expr: &Match, as_value: bool, rewriter: &mut Rewriter) -> Result<(), Error> {
+    let arms = reachable(expr);
+    for arm in &expr.arms[arms.len()..] {
+        rewriter.warn(Warning::UnreachableMatchArm(arm.span));
+    }
+    if arms.iter().any(has_wildcard) {
+        return Ok(());
+    }
+    let members: Option<Vec<_>> = expr.arms
+        .iter()
+        .flat_map(|arm| arm.patterns.iter())
+        .map(|pattern| enum_member(pattern, rewriter))
+        .collect();
+    match members {
+        Some(ref members) if !members.is_empty() => {
+            let name = members[0].0;
+            let declared = rewriter.symbols().enum_members(name).unwrap();
+            if members.iter().all(|&(enum_name, _)| enum_name == name)
+            && declared.iter().all(|declared| members.iter().any(|&(_, member)| member == declared)) {
+                Ok(())
+            } else {
+                Err(Error::RewriteError(RewriteError::NonExhaustiveMatch).at(expr.value.span()))
+            }
+        }
+        _ if as_value => Err(Error::RewriteError(RewriteError::NonExhaustiveMatch).at(expr.value.span())),
+        _ => Ok(()),
+    }
+}
+
+/// Lowers a match to a switch when every pattern is a constant, or an if/else chain otherwise. When
+/// there is a result variable, the value of each arm is assigned to it
+fn lower(expr: &Match, result: Option<&str>, rewriter: &mut Rewriter) -> Result<Vec<Statement>, Error> {
+    let value = expr.value.rewrite(rewriter)?;
+    let reachable = reachable(expr);
+    let mut arms = vec![];
+    for arm in reachable {
+        // a wildcard is a `None` label, the same as the default case of a switch
+        let mut labels = vec![];
+        for pattern in &arm.patterns {
+            labels.push(match pattern {
+                Pattern::Wildcard => None,
+                Pattern::Value(ref value) => Some(value.rewrite(rewriter)?),
+            });
+        }
+        let body = match (result, &arm.body.kind) {
+            (Some(result), ast::StatementKind::Expression(ref value)) => {
+                // anything hoisted from the arm must only run when the arm is chosen
+                let (mut body, value) = rewriter.isolated(|rewriter| value.rewrite(rewriter))?;
+                body.push(Statement::Assignment(LValue::Identifier(result.to_owned()), value));
+                body
+            }
+            (Some(..), _) => return Err(Error::RewriteError(RewriteError::MatchArmNotExpression).at(arm.span)),
+            (None, _) => arm.body.rewrite(rewriter)?,
+        };
+        arms.push((labels, body));
+    }
+
+    let constant = reachable.iter().all(|arm| arm.patterns.iter().all(|pattern| is_constant(pattern, rewriter)));
+    if constant && !reachable.iter().any(|arm| breaks(&arm.body)) {
+        let mut cases = vec![];
+        for (mut labels, mut body) in arms {
+            body.push(Statement::Break);
+            let last = labels.pop().unwrap();
+            cases.extend(labels.into_iter().map(|label| Case { label, body: vec![] }));
+            cases.push(Case { label: last, body });
+        }
+        return Ok(vec![Statement::Switch(value, cases)]);
+    }
+
+    let mut statements = vec![];
+    let subject = match value {
+        Expression::Identifier(..) => value,
+        value => {
+            // the value is only evaluated once, no matter how many arms it is compared against
+            let temporary = rewriter.temporary();
+            statements.push(Statement::VarDecl(temporary.clone(), Some(value)));
+            Expression::Identifier(temporary)
+        }
+    };
+    let mut chain = None;
+    for (labels, body) in arms.into_iter().rev() {
+        let body = Statement::from_vec(body);
+        let labels: Option<Vec<_>> = labels.into_iter().collect();
+        chain = Some(match labels {
+            None => body,
+            Some(labels) => {
+                let mut conditions = labels
+                    .into_iter()
+                    .map(|label| Expression::binary(Operator::Eq, subject.clone(), label));
+                let first = conditions.next().unwrap();
+                let cond = conditions.fold(first, |cond, next| Expression::binary(Operator::BOr, cond, next));
+                Statement::If(cond, box body, chain.map(Box::new))
+            }
+        });
+    }
+    statements.extend(chain);
+    Ok(statements)
+}
+
+impl Rewrite for Match {
+    type Output = Expression;
+    fn rewrite(&self, rewriter: &mut Rewriter) -> Result<Expression, Error> {
+        check_exhaustive(self, true, rewriter)?;
+        let result = rewriter.temporary();
+        let mut statements = vec![Statement::VarDecl(result.clone(), None)];
+        statements.extend(lower(self, Some(&result), rewriter)?);
+        rewriter.hoist(statements);
+        Ok(Expression::Identifier(result))
+    }
+}
+
+/// Rewrites a match which is used as a statement, so the values of its arms are discarded
+pub fn statement(expr: &Match, rewriter: &mut Rewriter) -> Result<Vec<Statement>, Error> {
+    check_exhaustive(expr, false, rewriter)?;
+    lower(expr, None, rewriter)
+}
+
+#[cfg(test)]
+mod tests {
+    use gmlpp;
+    use gml::AST;
+    use symbols::Symbols;
+    use super::super::{lowered, lowered_with};
+
+    #[test]
+    fn constant_patterns_are_lowered_to_a_switch() {
+        assert_eq!(
+            lowered("x = match y {\n    1 | 2 => \"small\",\n    -1 => \"negative\",\n    _ => \"big\"\n}\n").unwrap(),
+            concat!(
+                "var _gmlpp_1;\n",
+                "switch (y) {\n",
+                "    case 1:\n",
+                "    case 2:\n",
+                "        _gmlpp_1 = \"small\";\n",
+                "        break;\n",
+                "    case -1:\n",
+                "        _gmlpp_1 = \"negative\";\n",
+                "        break;\n",
+                "    default:\n",
+                "        _gmlpp_1 = \"big\";\n",
+                "        break;\n",
+                "}\n",
+                "x = _gmlpp_1;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn other_patterns_are_lowered_to_an_if_else_chain() {
+        assert_eq!(
+            lowered("match f() {\n    a => {\n        g()\n    }\n    b.c => {\n        h()\n    }\n}\n").unwrap(),
+            concat!(
+                "var _gmlpp_1 = f();\n",
+                "if (_gmlpp_1 == a) {\n",
+                "    g();\n",
+                "} else if (_gmlpp_1 == b.c) {\n",
+                "    h();\n",
+                "}\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn members_of_an_enum_are_constant_patterns() {
+        assert_eq!(
+            lowered_with("enum Dir { Up, Down }\n", "x = match d {\n    Dir.Up => 1,\n    Dir.Down => 2\n}\n").unwrap(),
+            concat!(
+                "var _gmlpp_1;\n",
+                "switch (d) {\n",
+                "    case Dir.Up:\n",
+                "        _gmlpp_1 = 1;\n",
+                "        break;\n",
+                "    case Dir.Down:\n",
+                "        _gmlpp_1 = 2;\n",
+                "        break;\n",
+                "}\n",
+                "x = _gmlpp_1;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn match_used_as_a_statement_need_not_be_exhaustive() {
+        assert!(lowered("match y {\n    1 => {\n        f()\n    }\n}\n").is_ok());
+    }
+
+    #[test]
+    fn match_used_as_a_value_must_be_exhaustive() {
+        assert_eq!(lowered("x = match y {\n    1 => 2,\n    3 => 4\n}\n").unwrap_err().code(), "non-exhaustive-match");
+    }
+
+    #[test]
+    fn match_missing_a_member_of_an_enum_is_not_exhaustive() {
+        assert_eq!(lowered_with("enum Dir { Up, Down }\n", "x = match d {\n    Dir.Up => 1\n}\n").unwrap_err().code(), "non-exhaustive-match");
+    }
+
+    #[test]
+    fn arms_after_a_wildcard_are_unreachable() {
+        let source = "x = match y {\n    1 => 2,\n    _ => 3,\n    4 => 5\n}\n";
+        let ast = gmlpp::AST::from_reader(source.as_bytes()).unwrap();
+        let ast = AST::from_gmlpp(&ast, &Symbols::new()).unwrap();
+        let warnings: Vec<_> = ast.warnings().iter().map(|warning| (warning.code(), warning.span().start.line)).collect();
+        assert_eq!(warnings, vec![("unreachable-match-arm", 4)]);
+    }
+
+    #[test]
+    fn arms_after_a_wildcard_are_left_out() {
+        assert_eq!(
+            lowered("x = match y {\n    1 => 2,\n    _ => 3,\n    4 => 5\n}\n").unwrap(),
+            concat!(
+                "var _gmlpp_1;\n",
+                "switch (y) {\n",
+                "    case 1:\n",
+                "        _gmlpp_1 = 2;\n",
+                "        break;\n",
+                "    default:\n",
+                "        _gmlpp_1 = 3;\n",
+                "        break;\n",
+                "}\n",
+                "x = _gmlpp_1;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn arm_which_is_not_an_expression_is_reported_at_the_arm() {
+        let error = lowered("x = match y {\n    1 => 2,\n    _ => { z = 3 }\n}\n").unwrap_err();
+        assert_eq!(error.code(), "match-arm-not-expression");
+        assert_eq!(error.span().map(|span| span.start.line), Some(3));
+    }
+}
@@ -0,0 +1,103 @@
+//! Lowers the GML++ syntax tree into the GML syntax tree
+
+use std::mem;
+
+use error::{Error, Warning};
+use symbols::Symbols;
+use super::ast::Statement;
+
+mod code;
+mod argument_list;
+mod statement;
+mod case;
+mod assignment;
+mod lvalue;
+mod expression;
+mod value;
+mod call;
+mod literal;
+mod identifier;
+mod matching;
+mod scope;
+mod iteration;
+
+use self::scope::Scope;
+
+/// A GML++ syntax tree node which can be converted into GML
+pub trait Rewrite {
+    type Output;
+    fn rewrite(&self, rewriter: &mut Rewriter) -> Result<Self::Output, Error>;
+}
+
+/// The state carried through the rewriting of a single file
+#[derive(Debug)]
+pub struct Rewriter<'a> {
+    symbols: &'a Symbols,
+    temporaries: usize,
+    warnings: Vec<Warning>,
+    hoisted: Vec<Statement>,
+    scopes: Vec<Scope>,
+}
+
+impl<'a> Rewriter<'a> {
+    pub fn new(symbols: &'a Symbols) -> Self {
+        Rewriter { symbols, temporaries: 0, warnings: vec![], hoisted: vec![], scopes: vec![Scope::new()] }
+    }
+
+    /// The declarations from the rest of the project
+    pub fn symbols(&self) -> &'a Symbols {
+        self.symbols
+    }
+
+    /// Records a problem which does not prevent the file from being rewritten
+    pub fn warn(&mut self, warning: Warning) {
+        self.warnings.push(warning);
+    }
+
+    /// Takes all the warnings that have been recorded so far
+    pub fn warnings(self) -> Vec<Warning> {
+        self.warnings
+    }
+
+    /// Adds statements which have to run before the statement currently being rewritten, for
+    /// expressions which can only be written in GML as statements
+    pub fn hoist(&mut self, statements: Vec<Statement>) {
+        self.hoisted.extend(statements);
+    }
+
+    /// Runs a rewrite, returning the statements it hoisted rather than leaving them to be placed before
+    /// the current statement
+    pub fn isolated<T, F>(&mut self, rewrite: F) -> Result<(Vec<Statement>, T), Error>
+    where F: FnOnce(&mut Self) -> Result<T, Error> {
+        let outer = mem::replace(&mut self.hoisted, vec![]);
+        let result = rewrite(self);
+        let hoisted = mem::replace(&mut self.hoisted, outer);
+        Ok((hoisted, result?))
+    }
+
+    /// Generates a fresh variable name for values introduced by the rewriter
+    pub fn temporary(&mut self) -> String {
+        self.temporaries += 1;
+        format!("_gmlpp_{}", self.temporaries)
+    }
+}
+
+/// Lowers a GML++ source file to the GML it compiles to, for testing the rewriting of each part
+#[cfg(test)]
+pub fn lowered(source: &str) -> Result<String, Error> {
+    use gmlpp;
+    let ast = gmlpp::AST::from_reader(source.as_bytes())?;
+    assert!(ast.errors().is_empty(), "{:?}", ast.errors());
+    super::AST::from_gmlpp(&ast, &Symbols::new()).map(|ast| ast.print())
+}
+
+/// Lowers a GML++ source file knowing about the enums declared in another, for testing the
+/// rewriting of the parts which depend on the rest of the project
+#[cfg(test)]
+pub fn lowered_with(declarations: &str, source: &str) -> Result<String, Error> {
+    use gmlpp;
+    let mut symbols = Symbols::new();
+    symbols.collect(None, &gmlpp::AST::from_reader(declarations.as_bytes())?);
+    let ast = gmlpp::AST::from_reader(source.as_bytes())?;
+    super::AST::from_gmlpp(&ast, &symbols).map(|ast| ast.print())
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use gmlpp::ast::{Identifier, Type};
+use error::{Error, RewriteError};
+use super::super::ast::{Expression, Literal};
+use super::Rewriter;
+
+/// A variable declared in the script
+#[derive(Clone, Debug)]
+pub struct Binding {
+    /// The name of the variable in the GML, which is changed so it cannot collide with another
+    pub name: String,
+    pub constant: bool,
+    /// The value of a constant, if it is a literal which can be inlined
+    pub value: Option<Literal>,
+    pub collection: Option<Collection>,
+}
+
+/// The kinds of data structure which can be iterated over
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Collection {
+    Array,
+    List,
+    Map,
+}
+
+impl Collection {
+    /// The kind of collection a variable holds, judging by its declared type or else the function
+    /// which created its value
+    fn of(ty: Option<Type>, value: Option<&Expression>) -> Option<Self> {
+        match ty {
+            Some(Type::Array) => return Some(Collection::Array),
+            Some(Type::List) => return Some(Collection::List),
+            Some(Type::Map) => return Some(Collection::Map),
+            _ => {}
+        }
+        match value {
+            Some(Expression::Call(ref name, _)) =>
+                match name.as_str() {
+                    "array_create" => Some(Collection::Array),
+                    "ds_list_create" => Some(Collection::List),
+                    "ds_map_create" => Some(Collection::Map),
+                    _ => None,
+                },
+            _ => None,
+        }
+    }
+}
+
+/// The variables declared in a single block
+pub type Scope = HashMap<String, Binding>;
+
+impl<'a> Rewriter<'a> {
+    /// Runs a rewrite in a new block scope, forgetting anything declared in it afterwards
+    pub fn scoped<T, F>(&mut self, rewrite: F) -> Result<T, Error>
+    where F: FnOnce(&mut Self) -> Result<T, Error> {
+        self.scopes.push(Scope::new());
+        let result = rewrite(self);
+        self.scopes.pop();
+        result
+    }
+
+    /// Declares a variable in the current scope, returning the name it will have in the GML. As a GML
+    /// `var` belongs to the whole script, and hides the instance variable of the same name everywhere in
+    /// it, every declaration is renamed so it cannot collide with either
+    pub fn declare(&mut self, ident: &Identifier, constant: bool, ty: Option<Type>, value: Option<&Expression>) -> Result<String, Error> {
+        let name = &ident.0;
+        if self.scopes.last().map_or(false, |scope| scope.contains_key(name)) {
+            return Err(Error::RewriteError(RewriteError::Redeclaration).at(ident.1));
+        }
+        let renamed = format!("{}_{}", self.temporary(), name);
+        let binding = Binding {
+            name: renamed.clone(),
+            constant,
+            value: match value {
+                Some(Expression::Literal(ref literal)) if constant => Some(literal.clone()),
+                _ => None,
+            },
+            collection: Collection::of(ty, value),
+        };
+        self.scopes.last_mut().unwrap().insert(name.to_owned(), binding);
+        Ok(renamed)
+    }
+
+    /// Records a `var`, which is never renamed, so that what it holds is known
+    pub fn declare_var(&mut self, name: &str, ty: Option<Type>, value: Option<&Expression>) {
+        let binding = Binding {
+            name: name.to_owned(),
+            constant: false,
+            value: None,
+            collection: Collection::of(ty, value),
+        };
+        self.scopes.last_mut().unwrap().insert(name.to_owned(), binding);
+    }
+
+    /// Finds the innermost declaration of a variable which is in scope
+    pub fn resolve(&self, name: &str) -> Option<&Binding> {
+        self.scopes.iter().rev().filter_map(|scope| scope.get(name)).next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::lowered;
+
+    #[test]
+    fn every_declaration_is_renamed() {
+        assert_eq!(
+            lowered("let x = 1\nif (x) {\n    let x = 2\n    let y = x\n}\nconst z = 3\nreturn x + z\n").unwrap(),
+            concat!(
+                "var _gmlpp_1_x = 1;\n",
+                "if (_gmlpp_1_x) {\n",
+                "    var _gmlpp_2_x = 2;\n",
+                "    var _gmlpp_3_y = _gmlpp_2_x;\n",
+                "}\n",
+                "var _gmlpp_4_z = 3;\n",
+                "return _gmlpp_1_x + 3;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn constant_cannot_be_assigned() {
+        assert_eq!(lowered("const x = 1\nx = 2\n").unwrap_err().code(), "assignment-to-constant");
+    }
+
+    #[test]
+    fn let_sharing_its_name_with_an_earlier_var_is_renamed() {
+        assert_eq!(
+            lowered("if (a) {\n    var x = 1\n}\nlet x = 2\nreturn x\n").unwrap(),
+            concat!(
+                "if (a) {\n",
+                "    var x = 1;\n",
+                "}\n",
+                "var _gmlpp_1_x = 2;\n",
+                "return _gmlpp_1_x;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn var_sharing_its_name_with_a_let_is_kept_apart() {
+        assert_eq!(
+            lowered("let x = 1\nif (a) {\n    var x = 2\n}\nreturn x\n").unwrap(),
+            concat!(
+                "var _gmlpp_1_x = 1;\n",
+                "if (a) {\n",
+                "    var x = 2;\n",
+                "}\n",
+                "return _gmlpp_1_x;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn var_can_be_declared_again() {
+        assert!(lowered("var x = 1\nvar x = 2\n").is_ok());
+    }
+}
@@ -0,0 +1,238 @@
+use gmlpp::ast;
+use error::{Error, RewriteError, Warning};
+use super::super::ast::{Statement, Simple, Expression, LValue, Literal, UnaryOperator};
+use super::{Rewrite, Rewriter};
+use super::matching;
+use super::iteration;
+
+/// Rewrites the body of a control structure into a single statement
+fn body(statement: &ast::Statement, rewriter: &mut Rewriter) -> Result<Box<Statement>, Error> {
+    Ok(box Statement::from_vec(statement.rewrite(rewriter)?))
+}
+
+/// Rewrites a loop condition, which is evaluated every iteration so cannot hoist anything out
+fn condition(cond: &ast::Expression, rewriter: &mut Rewriter) -> Result<Expression, Error> {
+    match rewriter.isolated(|rewriter| cond.rewrite(rewriter))? {
+        (ref hoisted, cond) if hoisted.is_empty() => Ok(cond),
+        _ => Err(Error::RewriteError(RewriteError::HoistInLoopHeader).at(cond.span())),
+    }
+}
+
+/// Rewrites the initializer or update of a `for` loop, which must stay a single simple statement
+fn header(statement: &ast::Statement, rewriter: &mut Rewriter) -> Result<Simple, Error> {
+    let statements = statement.rewrite(rewriter)?;
+    if statements.len() > 1 {
+        return Err(Error::RewriteError(RewriteError::UnsupportedLoopHeader).at(statement.span));
+    }
+    Simple::from_statement(Statement::from_vec(statements))
+        .ok_or(Error::RewriteError(RewriteError::UnsupportedLoopHeader).at(statement.span))
+}
+
+/// Negates a condition, for loops that GML only supports the opposite of
+fn not(cond: Expression) -> Expression {
+    Expression::Unary(UnaryOperator::Not, box cond)
+}
+
+/// Whether control can never continue past this statement
+fn diverges(statement: &ast::Statement) -> bool {
+    match statement.kind {
+        ast::StatementKind::Break | ast::StatementKind::Continue | ast::StatementKind::Return(..) | ast::StatementKind::Exit => true,
+        ast::StatementKind::Block(ref statements) => statements.iter().any(diverges),
+        ast::StatementKind::If(_, ref tbody, Some(ref fbody)) => diverges(tbody) && diverges(fbody),
+        _ => false,
+    }
+}
+
+impl Rewrite for ast::Statement {
+    type Output = Vec<Statement>;
+    fn rewrite(&self, rewriter: &mut Rewriter) -> Result<Vec<Statement>, Error> {
+        // the expressions in this statement may hoist statements which have to run before it
+        let (mut statements, rewritten) = rewriter.isolated(|rewriter| lower(self, rewriter))?;
+        statements.extend(rewritten);
+        Ok(statements)
+    }
+}
+
+/// Rewrites a statement, without the statements hoisted out of its expressions
+fn lower(statement: &ast::Statement, rewriter: &mut Rewriter) -> Result<Vec<Statement>, Error> {
+    let statement = match statement.kind {
+        ast::StatementKind::Noop => return Ok(vec![]),
+        ast::StatementKind::Assignment(ref assignment) => assignment.rewrite(rewriter)?,
+        ast::StatementKind::Expression(ref expr) => Statement::Expression(expr.rewrite(rewriter)?),
+        ast::StatementKind::VarDecl(ref ident, ref ty) => {
+            rewriter.declare_var(&ident.0, *ty, None);
+            Statement::VarDecl(ident.rewrite(rewriter)?, None)
+        }
+        ast::StatementKind::VarDeclAssign(ref ident, ref ty, ref value) => {
+            let value = value.rewrite(rewriter)?;
+            rewriter.declare_var(&ident.0, *ty, Some(&value));
+            Statement::VarDecl(ident.rewrite(rewriter)?, Some(value))
+        }
+        ast::StatementKind::GlobalvarDecl(ref ident) => Statement::GlobalvarDecl(ident.rewrite(rewriter)?),
+        ast::StatementKind::GlobalvarDeclAssign(ref ident, ref value) => {
+            // GML does not allow a globalvar to be initialized where it is declared
+            let name = ident.rewrite(rewriter)?;
+            let value = value.rewrite(rewriter)?;
+            return Ok(vec![
+                Statement::GlobalvarDecl(name.clone()),
+                Statement::Assignment(LValue::Identifier(name), value),
+            ]);
+        }
+        ast::StatementKind::LetDecl(ref ident, ref ty) => Statement::VarDecl(rewriter.declare(ident, false, *ty, None)?, None),
+        ast::StatementKind::LetDeclAssign(ref ident, ref ty, ref value) => {
+            // the value is rewritten first, as the new variable is not in scope until after it
+            let value = value.rewrite(rewriter)?;
+            Statement::VarDecl(rewriter.declare(ident, false, *ty, Some(&value))?, Some(value))
+        }
+        ast::StatementKind::ConstDecl(ref ident, ref ty, ref value) => {
+            let value = value.rewrite(rewriter)?;
+            Statement::VarDecl(rewriter.declare(ident, true, *ty, Some(&value))?, Some(value))
+        }
+        ast::StatementKind::EnumDecl(ref name, ref members) => {
+            let mut lowered = vec![];
+            for member in members {
+                let value = match member.value {
+                    Some(ref value) => Some(value.rewrite(rewriter)?),
+                    None => None,
+                };
+                lowered.push((member.name.rewrite(rewriter)?, value));
+            }
+            Statement::Enum(name.rewrite(rewriter)?, lowered)
+        }
+        ast::StatementKind::If(ref cond, ref tbody, None) => Statement::If(cond.rewrite(rewriter)?, body(tbody, rewriter)?, None),
+        ast::StatementKind::If(ref cond, ref tbody, Some(ref fbody)) =>
+            Statement::If(cond.rewrite(rewriter)?, body(tbody, rewriter)?, Some(body(fbody, rewriter)?)),
+        ast::StatementKind::While(ref cond, ref wbody) => Statement::While(condition(cond, rewriter)?, body(wbody, rewriter)?),
+        ast::StatementKind::DoWhile(ref dbody, ref cond) => Statement::DoUntil(body(dbody, rewriter)?, not(condition(cond, rewriter)?)),
+        ast::StatementKind::Until(ref cond, ref ubody) => Statement::While(not(condition(cond, rewriter)?), body(ubody, rewriter)?),
+        ast::StatementKind::DoUntil(ref dbody, ref cond) => Statement::DoUntil(body(dbody, rewriter)?, condition(cond, rewriter)?),
+        ast::StatementKind::Repeat(ref times, ref rbody) => Statement::Repeat(times.rewrite(rewriter)?, body(rbody, rewriter)?),
+        // anything declared in the header of a for loop is only in scope in the loop
+        ast::StatementKind::For(ref init, ref cond, ref update, ref fbody) => rewriter.scoped(|rewriter| {
+            Ok(Statement::For(
+                header(init, rewriter)?,
+                condition(cond, rewriter)?,
+                header(update, rewriter)?,
+                body(fbody, rewriter)?,
+            ))
+        })?,
+        ast::StatementKind::ForIn(ref ident, ref collection, ref fbody) => return iteration::for_in(ident, collection, fbody, rewriter),
+        ast::StatementKind::ForRange(ref ident, ref start, ref end, ref fbody) =>
+            return iteration::for_range(ident, start, end, fbody, rewriter),
+        ast::StatementKind::ForOf(ref ident, ref collection, ref fbody) => return iteration::for_of(ident, collection, fbody, rewriter),
+        ast::StatementKind::With(ref target, ref wbody) => Statement::With(target.rewrite(rewriter)?, body(wbody, rewriter)?),
+        ast::StatementKind::Loop(ref lbody) => Statement::While(Expression::Literal(Literal::Boolean(true)), body(lbody, rewriter)?),
+        ast::StatementKind::Block(ref statements) => rewriter.scoped(|rewriter| {
+            let mut block = vec![];
+            for statement in statements {
+                block.extend(statement.rewrite(rewriter)?);
+            }
+            Ok(Statement::Block(block))
+        })?,
+        ast::StatementKind::Switch(ref value, ref cases) => {
+            let value = value.rewrite(rewriter)?;
+            // the cases of a switch all share one scope, as they can fall through into each other
+            let lowered = rewriter.scoped(|rewriter| {
+                let mut lowered = vec![];
+                for (i, case) in cases.iter().enumerate() {
+                    if i + 1 != cases.len() && !case.fallthrough && !case.body.is_empty() && !case.body.iter().any(diverges) {
+                        rewriter.warn(Warning::ImplicitFallthrough(case.span));
+                    }
+                    lowered.push(case.rewrite(rewriter)?);
+                }
+                Ok(lowered)
+            })?;
+            Statement::Switch(value, lowered)
+        }
+        ast::StatementKind::Match(ref expr) => return matching::statement(expr, rewriter),
+        ast::StatementKind::Return(None) => Statement::Return(None),
+        ast::StatementKind::Return(Some(ref value)) => Statement::Return(Some(value.rewrite(rewriter)?)),
+        ast::StatementKind::Break => Statement::Break,
+        ast::StatementKind::Exit => Statement::Exit,
+        ast::StatementKind::Continue => Statement::Continue,
+    };
+    Ok(vec![statement])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::lowered;
+
+    #[test]
+    fn statements_end_with_semicolons() {
+        assert_eq!(
+            lowered("x = 1\nfoo(x, \"a\")\nvar y\n").unwrap(),
+            concat!(
+                "x = 1;\n",
+                "foo(x, \"a\");\n",
+                "var y;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn loops_gml_lacks_are_written_with_the_opposite_condition() {
+        assert_eq!(
+            lowered("until (done) {\n    step()\n}\ndo {\n    step()\n} while (running)\n").unwrap(),
+            concat!(
+                "while (!done) {\n",
+                "    step();\n",
+                "}\n",
+                "do {\n",
+                "    step();\n",
+                "} until (!running);\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn globalvar_is_declared_before_it_is_assigned() {
+        assert_eq!(
+            lowered("globalvar score = 0\n").unwrap(),
+            concat!(
+                "globalvar score;\n",
+                "score = 0;\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn enums_are_declared_with_their_values() {
+        assert_eq!(
+            lowered("enum Dir { Up, Down = 5, Left }\n").unwrap(),
+            concat!(
+                "enum Dir {\n",
+                "    Up,\n",
+                "    Down = 5,\n",
+                "    Left\n",
+                "}\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn loops_and_jumps_are_lowered() {
+        assert_eq!(
+            lowered("for (var i = 0; i < 10; i += 1) {\n    if (i == 5) {\n        continue\n    }\n    repeat (3) {\n        break\n    }\n}\nwith (obj_wall) {\n    exit\n}\nloop {\n    do {\n        x += 1\n    } while (x < 3)\n    return x\n}\n").unwrap(),
+            concat!(
+                "for (var i = 0; i < 10; i += 1) {\n",
+                "    if (i == 5) {\n",
+                "        continue;\n",
+                "    }\n",
+                "    repeat (3) {\n",
+                "        break;\n",
+                "    }\n",
+                "}\n",
+                "with (obj_wall) {\n",
+                "    exit;\n",
+                "}\n",
+                "while (true) {\n",
+                "    do {\n",
+                "        x += 1;\n",
+                "    } until (!(x < 3));\n",
+                "    return x;\n",
+                "}\n",
+            ),
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use gmlpp::ast::{self, Value};
+use error::{Error, RewriteError};
+use super::super::ast::{Expression, UnaryOperator};
+use super::{Rewrite, Rewriter};
+use super::lvalue::target;
+
+impl Rewrite for Value {
+    type Output = Expression;
+    fn rewrite(&self, rewriter: &mut Rewriter) -> Result<Expression, Error> {
+        match self {
+            Value::LValue(ref lvalue) => {
+                // constants with literal values are inlined wherever they are read
+                if let ast::LValue::Identifier(ref ident) = lvalue {
+                    if let Some(literal) = rewriter.resolve(&ident.0).and_then(|binding| binding.value.clone()) {
+                        return Ok(Expression::Literal(literal));
+                    }
+                }
+                Ok(lvalue.rewrite(rewriter)?.to_expression())
+            }
+            Value::Literal(ref literal) => Ok(Expression::Literal(literal.rewrite(rewriter)?)),
+            // parentheses are added back in by the printer wherever they are needed
+            Value::Expr(ref expr) => expr.rewrite(rewriter),
+            Value::Call(ref call) => call.rewrite(rewriter),
+            Value::Match(ref expr) => expr.rewrite(rewriter),
+            Value::Negative(ref inner) => Ok(Expression::Unary(UnaryOperator::Negative, box inner.rewrite(rewriter)?)),
+            Value::Not(ref inner) => Ok(Expression::Unary(UnaryOperator::Not, box inner.rewrite(rewriter)?)),
+            Value::Inverted(ref inner) => Ok(Expression::Unary(UnaryOperator::Inverted, box inner.rewrite(rewriter)?)),
+            Value::PreIncrement(ref lvalue) => Ok(Expression::PreIncrement(target(lvalue, rewriter)?)),
+            Value::PreDecrement(ref lvalue) => Ok(Expression::PreDecrement(target(lvalue, rewriter)?)),
+            Value::PostIncrement(ref lvalue) => Ok(Expression::PostIncrement(target(lvalue, rewriter)?)),
+            Value::PostDecrement(ref lvalue) => Ok(Expression::PostDecrement(target(lvalue, rewriter)?)),
+            // placeholders are replaced when rewriting a pipe, so any that make it here are not in a pipe
+            Value::Placeholder => Err(Error::RewriteError(RewriteError::MisplacedPlaceholder)),
+        }
+    }
+}
@@ -2,17 +2,18 @@ use std::fmt::{self, Display, Formatter};
 
 use super::identifier::Identifier;
 use super::expression::Expression;
+use super::ty::{Type, Annotation, annotation};
 use super::fragment::Fragment;
-use super::helpers::semi_or_eol;
+use super::helpers::{semi_or_eol, skip_eol};
 use super::super::tokenizer::{Token, Tokens};
 use error::Error;
 
 #[derive(Clone, Debug)]
 pub enum ArgumentList {
-    Argument(Identifier, Box<ArgumentList>),
-    DefaultArgument(Identifier, Expression, Box<ArgumentList>),
-    OptionalArgument(Identifier, Box<ArgumentList>),
-    VariadicArgument(Identifier),
+    Argument(Identifier, Option<Type>, Box<ArgumentList>),
+    DefaultArgument(Identifier, Option<Type>, Expression, Box<ArgumentList>),
+    OptionalArgument(Identifier, Option<Type>, Box<ArgumentList>),
+    VariadicArgument(Identifier, Option<Type>),
     End,
 }
 
@@ -20,10 +21,11 @@ impl Display for ArgumentList {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         use self::ArgumentList::*;
         match self {
-            &Argument(ref ident, ref rest) => write!(f, "argument {}\n{};", ident, rest),
-            &DefaultArgument(ref ident, ref expr, ref rest) => write!(f, "argument {} = {}\n{};", ident, expr, rest),
-            &OptionalArgument(ref ident, ref rest) => write!(f, "argument {}?\n{};", ident, rest),
-            &VariadicArgument(ref ident) => write!(f, "argument ...{}\n;", ident),
+            &Argument(ref ident, ref ty, ref rest) => write!(f, "argument {}{};\n{}", ident, Annotation(ty), rest),
+            &DefaultArgument(ref ident, ref ty, ref expr, ref rest) =>
+                write!(f, "argument {}{} = {};\n{}", ident, Annotation(ty), expr, rest),
+            &OptionalArgument(ref ident, ref ty, ref rest) => write!(f, "argument {}?{};\n{}", ident, Annotation(ty), rest),
+            &VariadicArgument(ref ident, ref ty) => write!(f, "argument ...{}{};\n", ident, Annotation(ty)),
             &End => write!(f, ""),
         }
     }
@@ -32,40 +34,42 @@ impl Display for ArgumentList {
 impl Fragment for ArgumentList {
     fn parse(tokens: &Tokens) -> Result<Self, Error> {
         use self::ArgumentList::*;
+        skip_eol(tokens);
         match tokens[..2] {
             [Token::Argument, Token::DotDotDot] => {
                 tokens.skip(2);
                 let ident = Identifier::parse(tokens)?;
+                // the type of a variadic argument is the type of each of the values
+                let ty = annotation(tokens)?;
                 semi_or_eol(tokens)?;
-                Ok(VariadicArgument(ident))
+                Ok(VariadicArgument(ident, ty))
             }
             [Token::Argument, ..] => {
                 tokens.skip(1);
                 let ident = Identifier::parse(tokens)?;
+                if tokens.peek() == Token::Question {
+                    tokens.skip(1);
+                    let ty = annotation(tokens)?;
+                    semi_or_eol(tokens)?;
+                    let rest = Self::parse(tokens)?;
+                    return Ok(OptionalArgument(ident, ty, box rest));
+                }
+                let ty = annotation(tokens)?;
                 match tokens.peek() {
-                    Token::Question => {
-                        tokens.skip(1);
-                        semi_or_eol(tokens)?;
-                        let rest = Self::parse(tokens)?;
-                        Ok(OptionalArgument(ident, box rest))
-                    }
                     Token::Assign => {
                         tokens.skip(1);
                         let expr = Expression::parse(tokens)?;
                         semi_or_eol(tokens)?;
                         let rest = Self::parse(tokens)?;
-                        Ok(DefaultArgument(ident, expr, box rest))
+                        Ok(DefaultArgument(ident, ty, expr, box rest))
                     }
                     _ => {
                         semi_or_eol(tokens)?;
-                        Ok(Argument(ident, box Self::parse(tokens)?))
+                        Ok(Argument(ident, ty, box Self::parse(tokens)?))
                     }
                 }
             }
-            _ => {
-                eprintln!("Failed to match ArgumentList: {:?}", tokens[..2].to_vec());
-                Ok(End)
-            },
+            _ => Ok(End),
         }
     }
 }
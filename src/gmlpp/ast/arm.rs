@@ -0,0 +1,81 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::value::Value;
+use super::expression::Expression;
+use super::statement::{Statement, StatementKind};
+use super::fragment::Fragment;
+use super::super::tokenizer::{Token, Tokens, Span};
+use error::{Error, ParseError};
+
+/// One arm of a match, which is run when the matched value is equal to any of its patterns
+#[derive(Clone, Debug)]
+pub struct Arm {
+    pub patterns: Vec<Pattern>,
+    /// Either a block, or an expression statement when the arm is just a value
+    pub body: Box<Statement>,
+    pub span: Span,
+}
+
+/// A value to compare against in a match. The wildcard `_` matches anything
+#[derive(Clone, Debug)]
+pub enum Pattern {
+    Wildcard,
+    Value(Value),
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Value(ref value) => value.fmt(f),
+        }
+    }
+}
+
+impl Fragment for Pattern {
+    fn parse(tokens: &Tokens) -> Result<Self, Error> {
+        // patterns are only single values, as `|` separates the alternatives
+        match Value::parse(tokens)? {
+            Value::Placeholder => Ok(Pattern::Wildcard),
+            value => Ok(Pattern::Value(value)),
+        }
+    }
+}
+
+impl Display for Arm {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let indent = f.precision().unwrap_or(0);
+        for (i, pattern) in self.patterns.iter().enumerate() {
+            if i != 0 {
+                write!(f, " | ")?;
+            }
+            write!(f, "{}", pattern)?;
+        }
+        match self.body.kind {
+            StatementKind::Expression(ref expr) => write!(f, " => {}", expr),
+            _ => write!(f, " => {0:.1$}", self.body, indent),
+        }
+    }
+}
+
+impl Fragment for Arm {
+    fn parse(tokens: &Tokens) -> Result<Self, Error> {
+        let start = tokens.pos();
+        let mut patterns = vec![Pattern::parse(tokens)?];
+        while tokens.peek() == Token::Or {
+            tokens.skip(1);
+            patterns.push(Pattern::parse(tokens)?);
+        }
+        if tokens.next() != Token::Arrow {
+            return Err(Error::ParseError(ParseError::ExpectedArrow));
+        }
+        let body = if tokens.peek() == Token::LBrace {
+            Statement::parse(tokens)?
+        } else {
+            let expression = Expression::parse(tokens)?;
+            let span = expression.span();
+            Statement::new(StatementKind::Expression(expression), span)
+        };
+        Ok(Arm { patterns, body: box body, span: tokens.span_from(start) })
+    }
+}
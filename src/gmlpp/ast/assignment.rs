@@ -8,23 +8,63 @@ use error::{Error, ParseError};
 
 #[derive(Clone, Debug)]
 pub enum Assignment {
-    Eq(LValue, Expression)
+    Eq(LValue, Expression),
+    Plus(LValue, Expression),
+    Minus(LValue, Expression),
+    Times(LValue, Expression),
+    Slash(LValue, Expression),
+    Pct(LValue, Expression),
+    Exp(LValue, Expression),
+    And(LValue, Expression),
+    Or(LValue, Expression),
+    Xor(LValue, Expression),
+    LShift(LValue, Expression),
+    RShift(LValue, Expression),
 }
 
 impl Display for Assignment {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use self::Assignment::*;
         match self {
-            Assignment::Eq(ref lvalue, ref expr) => write!(f, "{} = {}", lvalue, expr),
+            Eq(ref lvalue, ref expr) => write!(f, "{} = {}", lvalue, expr),
+            Plus(ref lvalue, ref expr) => write!(f, "{} += {}", lvalue, expr),
+            Minus(ref lvalue, ref expr) => write!(f, "{} -= {}", lvalue, expr),
+            Times(ref lvalue, ref expr) => write!(f, "{} *= {}", lvalue, expr),
+            Slash(ref lvalue, ref expr) => write!(f, "{} /= {}", lvalue, expr),
+            Pct(ref lvalue, ref expr) => write!(f, "{} %= {}", lvalue, expr),
+            Exp(ref lvalue, ref expr) => write!(f, "{} **= {}", lvalue, expr),
+            And(ref lvalue, ref expr) => write!(f, "{} &= {}", lvalue, expr),
+            Or(ref lvalue, ref expr) => write!(f, "{} |= {}", lvalue, expr),
+            Xor(ref lvalue, ref expr) => write!(f, "{} ^= {}", lvalue, expr),
+            LShift(ref lvalue, ref expr) => write!(f, "{} <<= {}", lvalue, expr),
+            RShift(ref lvalue, ref expr) => write!(f, "{} >>= {}", lvalue, expr),
         }
     }
 }
 
 impl Fragment for Assignment {
     fn parse(tokens: &Tokens) -> Result<Self, Error> {
+        use self::Assignment::*;
         let lvalue = LValue::parse(tokens)?;
-        let _op = tokens.next();
+        let assignment = match tokens.next() {
+            Token::Assign => Eq,
+            Token::PlusEqual => Plus,
+            Token::MinusEqual => Minus,
+            Token::StarEqual => Times,
+            Token::SlashEqual => Slash,
+            Token::PctEqual => Pct,
+            Token::ExpEqual => Exp,
+            Token::AndEqual => And,
+            Token::OrEqual => Or,
+            Token::XorEqual => Xor,
+            Token::LShiftEqual => LShift,
+            Token::RShiftEqual => RShift,
+            _ => {
+                tokens.back(1);
+                return Err(Error::ParseError(ParseError::ExpectedAssignment));
+            }
+        };
         let expr = Expression::parse(tokens)?;
-        // TODO: use op, not just eq always
-        Ok(Assignment::Eq(lvalue, expr))
+        Ok(assignment(lvalue, expr))
     }
 }
@@ -2,14 +2,15 @@ use std::fmt::{self, Display, Formatter};
 
 use super::identifier::Identifier;
 use super::comma_list::CommaList;
-use super::super::tokenizer::{Token, Tokens};
+use super::super::tokenizer::{Token, Tokens, Span};
 use super::fragment::Fragment;
 use error::{Error, ParseError};
 
 #[derive(Clone, Debug)]
 pub struct Call {
-    name: Identifier,
-    arguments: CommaList,
+    pub name: Identifier,
+    pub arguments: CommaList,
+    pub span: Span,
 }
 
 impl Display for Call {
@@ -28,11 +29,10 @@ impl Fragment for Call {
         }
         let arguments = CommaList::parse(tokens)?;
         if tokens.next() != Token::RParen {
-            tokens.back(1);
-            eprintln!("Failed to match Call: {:?}", tokens.peek());
             tokens.seek(pos);
-            return Err(Error::ParseError(ParseError::ExpectedArgument))
+            return Err(Error::ParseError(ParseError::ExpectedArgument)
+                .context(format!("while parsing the arguments to {}", name)))
         }
-        Ok(Self { name, arguments })
+        Ok(Self { name, arguments, span: tokens.span_from(pos) })
     }
 }
@@ -0,0 +1,69 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::expression::Expression;
+use super::statement::Statement;
+use super::fragment::Fragment;
+use super::helpers::recovering;
+use super::super::tokenizer::{Token, Tokens, Span};
+use error::{Error, ParseError};
+
+/// A single labelled case of a switch statement. A `None` label is the default case
+#[derive(Clone, Debug)]
+pub struct Case {
+    pub label: Option<Expression>,
+    pub body: Vec<Statement>,
+    /// Whether there was a comment marking that this case intentionally falls through to the next
+    pub fallthrough: bool,
+    /// Where the label of the case was written
+    pub span: Span,
+}
+
+impl Display for Case {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let indent = f.precision().unwrap_or(0);
+        match self.label {
+            Some(ref label) => writeln!(f, "{0:1$}case {2}:", "", indent, label)?,
+            None => writeln!(f, "{0:1$}default:", "", indent)?,
+        }
+        for statement in &self.body {
+            write!(f, "{0:.1$}", statement, indent + 4)?;
+        }
+        if self.fallthrough {
+            writeln!(f, "{0:1$}// fallthrough", "", indent + 4)?;
+        }
+        Ok(())
+    }
+}
+
+impl Fragment for Case {
+    fn parse(tokens: &Tokens) -> Result<Self, Error> {
+        let start = tokens.pos();
+        let label = match tokens.next() {
+            Token::Case => Some(Expression::parse(tokens)?),
+            Token::Default => None,
+            _ => {
+                tokens.back(1);
+                return Err(Error::ParseError(ParseError::ExpectedCase));
+            }
+        };
+        if tokens.next() != Token::Colon {
+            return Err(Error::ParseError(ParseError::ExpectedColon));
+        }
+        let span = tokens.span_from(start);
+        let mut body = vec![];
+        let mut fallthrough = false;
+        loop {
+            match tokens.peek() {
+                Token::EOL => tokens.skip(1),
+                Token::FallthroughComment => {
+                    fallthrough = true;
+                    tokens.skip(1);
+                }
+                Token::Case | Token::Default | Token::RBrace => break,
+                Token::EOF => return Err(Error::UnexpectedEOF),
+                _ => body.push(recovering(tokens)),
+            }
+        }
+        Ok(Case { label, body, fallthrough, span })
+    }
+}
@@ -4,19 +4,31 @@ use super::fragment::Fragment;
 use super::argument_list::ArgumentList;
 use super::statements::Statements;
 use super::doc_comment::DocComment;
+use super::ty::Type;
+use super::helpers::semi_or_eol;
 use super::super::tokenizer::{Token, Tokens};
 use error::Error;
 
 #[derive(Clone, Debug)]
 pub struct Code {
-    docs: DocComment,
-    args: ArgumentList,
-    body: Statements,
+    pub docs: DocComment,
+    pub args: ArgumentList,
+    pub returns: Option<Type>,
+    pub body: Statements,
 }
 
 impl Display for Code {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}\n{}", self.args, self.body)
+        write!(f, "{}{}", self.docs, self.args)?;
+        if let Some(returns) = self.returns {
+            writeln!(f, "return: {};", returns)?;
+        }
+        // the header is kept apart from the body
+        match (&self.args, self.returns) {
+            (&ArgumentList::End, None) => {}
+            _ => writeln!(f)?,
+        }
+        write!(f, "{}", self.body)
     }
 }
 
@@ -25,8 +37,18 @@ impl Fragment for Code {
         assert_eq!(tokens[0], Token::BOF);
         tokens.skip(1);
         let docs = DocComment::parse(tokens)?;
-        let args = ArgumentList::parse(tokens)?;
+        let args = ArgumentList::parse(tokens).map_err(|error| error.context("while parsing the argument list"))?;
+        // the return type is declared after the arguments, as `return: Type`
+        let returns = match tokens[..2] {
+            [Token::Return, Token::Colon] => {
+                tokens.skip(2);
+                let returns = Type::parse(tokens).map_err(|error| error.context("while parsing the return type"))?;
+                semi_or_eol(tokens)?;
+                Some(returns)
+            }
+            _ => None,
+        };
         let body = Statements::parse(tokens)?;
-        Ok(Self { docs, args, body })
+        Ok(Self { docs, args, returns, body })
     }
 }
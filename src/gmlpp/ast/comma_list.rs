@@ -6,12 +6,15 @@ use super::super::tokenizer::{Token, Tokens};
 use error::Error;
 
 #[derive(Clone, Debug)]
-pub struct CommaList(Vec<Expression>);
+pub struct CommaList(pub Vec<Expression>);
 
 impl Display for CommaList {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        for item in &self.0 {
-            write!(f, "{}, ", item)?;
+        for (i, item) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", item)?;
         }
         Ok(())
     }
@@ -27,7 +30,6 @@ impl Fragment for CommaList {
                 // just pretend it's ok? someone else will have problems later instead!
                 Err(..) => {
                     tokens.seek(start);
-                    eprintln!("Failed to match CommaList: {:?}", tokens.peek());
                     break;
                 },
             }
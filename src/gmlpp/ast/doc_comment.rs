@@ -5,12 +5,13 @@ use super::super::tokenizer::{Token, Tokens};
 use error::Error;
 
 #[derive(Clone, Debug)]
-pub struct DocComment(Vec<String>);
+pub struct DocComment(pub Vec<String>);
 
 impl Display for DocComment {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         for line in self.0.iter() {
-            write!(f, "/// {}", line)?;
+            // the comment is kept exactly as it was written, slashes included
+            writeln!(f, "{}", line)?;
         }
         Ok(())
     }
@@ -0,0 +1,36 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::identifier::Identifier;
+use super::expression::Expression;
+use super::fragment::Fragment;
+use super::super::tokenizer::{Token, Tokens};
+use error::Error;
+
+/// A member of an enum declaration, optionally with an explicit value
+#[derive(Clone, Debug)]
+pub struct EnumMember {
+    pub name: Identifier,
+    pub value: Option<Expression>,
+}
+
+impl Display for EnumMember {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.value {
+            Some(ref value) => write!(f, "{} = {}", self.name, value),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl Fragment for EnumMember {
+    fn parse(tokens: &Tokens) -> Result<Self, Error> {
+        let name = Identifier::parse(tokens)?;
+        let value = if tokens.peek() == Token::Assign {
+            tokens.skip(1);
+            Some(Expression::parse(tokens)?)
+        } else {
+            None
+        };
+        Ok(EnumMember { name, value })
+    }
+}
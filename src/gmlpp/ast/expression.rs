@@ -3,7 +3,7 @@ use std::fmt::{self, Display, Formatter};
 use super::value::Value;
 use super::call::Call;
 use super::fragment::Fragment;
-use super::super::tokenizer::{Token, Tokens};
+use super::super::tokenizer::{Token, Tokens, Span};
 use error::{Error, ParseError};
 
 #[derive(Clone, Debug)]
@@ -31,7 +31,7 @@ pub enum Expression {
     Geq(Box<Expression>, Box<Expression>),
     Pipe(Box<Expression>, Call),
     Ternary(Box<Expression>, Box<Expression>, Box<Expression>),
-    Value(Value),
+    Value(Value, Span),
 }
 
 impl Display for Expression {
@@ -61,19 +61,18 @@ impl Display for Expression {
             Geq(ref lhs, ref rhs) => write!(f, "{} >= {}", lhs, rhs),
             Pipe(ref lhs, ref rhs) => write!(f, "{} |> {}", lhs, rhs),
             Ternary(ref cond, ref first, ref second) => write!(f, "{} ? {} : {}", cond, first, second),
-            Value(ref val) => write!(f, "{}", val),
+            Value(ref val, _) => write!(f, "{}", val),
         }
     }
 }
 
 impl Fragment for Expression {
     fn parse(tokens: &Tokens) -> Result<Self, Error> {
-        let value = Value::parse(tokens)?;
+        let value = Self::value(tokens)?;
         let pos = tokens.pos();
-        match Self::prec_parse(tokens, Expression::Value(value), Precedence::default()) {
+        match Self::prec_parse(tokens, value, Precedence::default()) {
             Err(error) => {
                 tokens.seek(pos);
-                eprintln!("Failed to match Expression: {}", error);
                 Err(error)
             }
             Ok(expr) => Ok(expr),
@@ -82,58 +81,80 @@ impl Fragment for Expression {
 }
 
 impl Expression {
-    fn prec_parse(tokens: &Tokens, lhs: Expression, min_prec: Precedence) -> Result<Self, Error> {
-        let op = tokens.peek();
-        match Precedence::of(&op) {
-            Some(op_prec) if op_prec >= min_prec => {
-                tokens.skip(1);
-                if op == Token::Pipe {
-                    // the pipe is a special case because the rhs needs to be just one call
-                    let pipe = Expression::Pipe(box lhs, Call::parse(tokens)?);
-                    Self::prec_parse(tokens, pipe, op_prec)
-                } else if op == Token::Question {
-                    // the question is special because it has three components
-                    let first = Expression::parse(tokens)?;
-                    if tokens.next() != Token::Colon { return Err(Error::ParseError(ParseError::IncompleteTernaryOperator)) }
-                    let second = Expression::parse(tokens)?;
-                    let ternary = Expression::Ternary(box lhs, box first, box second);
-                    Self::prec_parse(tokens, ternary, op_prec)
-                } else {
-                    let mut rhs = Expression::Value(Value::parse(tokens)?);
-                    loop {
-                        match Precedence::of(&tokens.peek()) {
-                            Some(peek_prec) if peek_prec > op_prec =>
-                                rhs = Self::prec_parse(tokens, rhs, peek_prec)?,
-                            _ => break,
-                        }
-                    }
-                    match op {
-                        Token::Plus => Ok(Expression::Plus(box lhs, box rhs)),
-                        Token::Minus => Ok(Expression::Minus(box lhs, box rhs)),
-                        Token::Slash => Ok(Expression::Slash(box lhs, box rhs)),
-                        Token::Star => Ok(Expression::Times(box lhs, box rhs)),
-                        Token::Pct => Ok(Expression::Pct(box lhs, box rhs)),
-                        Token::Exp => Ok(Expression::Exp(box lhs, box rhs)),
-                        Token::NotEqual => Ok(Expression::Neq(box lhs, box rhs)),
-                        Token::Equal => Ok(Expression::Eq(box lhs, box rhs)),
-                        Token::Less => Ok(Expression::Lt(box lhs, box rhs)),
-                        Token::More => Ok(Expression::Gt(box lhs, box rhs)),
-                        Token::LessEqual => Ok(Expression::Leq(box lhs, box rhs)),
-                        Token::MoreEqual => Ok(Expression::Geq(box lhs, box rhs)),
-                        Token::Div => Ok(Expression::Div(box lhs, box rhs)),
-                        Token::Mod => Ok(Expression::Mod(box lhs, box rhs)),
-                        Token::LShift => Ok(Expression::LShift(box lhs, box rhs)),
-                        Token::RShift => Ok(Expression::RShift(box lhs, box rhs)),
-                        Token::Or => Ok(Expression::Or(box lhs, box rhs)),
-                        Token::Xor => Ok(Expression::Xor(box lhs, box rhs)),
-                        Token::And => Ok(Expression::And(box lhs, box rhs)),
-                        Token::BAnd => Ok(Expression::BAnd(box lhs, box rhs)),
-                        Token::BOr => Ok(Expression::BOr(box lhs, box rhs)),
-                        _ => panic!("Unreachable! {:?} is not an operator", op),
+    /// Where this expression was written in the source file
+    pub fn span(&self) -> Span {
+        use self::Expression::*;
+        match self {
+            Plus(ref lhs, ref rhs) | Minus(ref lhs, ref rhs) | Times(ref lhs, ref rhs)
+            | Slash(ref lhs, ref rhs) | Pct(ref lhs, ref rhs) | Exp(ref lhs, ref rhs)
+            | Mod(ref lhs, ref rhs) | Div(ref lhs, ref rhs) | LShift(ref lhs, ref rhs)
+            | RShift(ref lhs, ref rhs) | And(ref lhs, ref rhs) | Or(ref lhs, ref rhs)
+            | Xor(ref lhs, ref rhs) | BAnd(ref lhs, ref rhs) | BOr(ref lhs, ref rhs)
+            | Eq(ref lhs, ref rhs) | Neq(ref lhs, ref rhs) | Lt(ref lhs, ref rhs)
+            | Gt(ref lhs, ref rhs) | Leq(ref lhs, ref rhs) | Geq(ref lhs, ref rhs) => lhs.span().to(rhs.span()),
+            Pipe(ref lhs, ref call) => lhs.span().to(call.span),
+            Ternary(ref cond, _, ref second) => cond.span().to(second.span()),
+            Value(_, ref span) => *span,
+        }
+    }
+
+    fn value(tokens: &Tokens) -> Result<Self, Error> {
+        let pos = tokens.pos();
+        let value = Value::parse(tokens)?;
+        Ok(Expression::Value(value, tokens.span_from(pos)))
+    }
+
+    fn prec_parse(tokens: &Tokens, mut lhs: Expression, min_prec: Precedence) -> Result<Self, Error> {
+        loop {
+            let op = tokens.peek();
+            let op_prec = match Precedence::of(&op) {
+                Some(op_prec) if op_prec >= min_prec => op_prec,
+                _ => return Ok(lhs),
+            };
+            tokens.skip(1);
+            lhs = if op == Token::Pipe {
+                // the pipe is a special case because the rhs needs to be just one call
+                Expression::Pipe(box lhs, Call::parse(tokens)?)
+            } else if op == Token::Question {
+                // the question is special because it has three components
+                let first = Expression::parse(tokens)?;
+                if tokens.next() != Token::Colon { return Err(Error::ParseError(ParseError::IncompleteTernaryOperator)) }
+                let second = Expression::parse(tokens)?;
+                Expression::Ternary(box lhs, box first, box second)
+            } else {
+                let mut rhs = Self::value(tokens)?;
+                loop {
+                    match Precedence::of(&tokens.peek()) {
+                        Some(peek_prec) if peek_prec > op_prec || peek_prec == op_prec && op_prec.is_right_associative() =>
+                            rhs = Self::prec_parse(tokens, rhs, peek_prec)?,
+                        _ => break,
                     }
                 }
-            }
-            _ => Ok(lhs),
+                match op {
+                    Token::Plus => Expression::Plus(box lhs, box rhs),
+                    Token::Minus => Expression::Minus(box lhs, box rhs),
+                    Token::Slash => Expression::Slash(box lhs, box rhs),
+                    Token::Star => Expression::Times(box lhs, box rhs),
+                    Token::Pct => Expression::Pct(box lhs, box rhs),
+                    Token::Exp => Expression::Exp(box lhs, box rhs),
+                    Token::NotEqual => Expression::Neq(box lhs, box rhs),
+                    Token::Equal => Expression::Eq(box lhs, box rhs),
+                    Token::Less => Expression::Lt(box lhs, box rhs),
+                    Token::More => Expression::Gt(box lhs, box rhs),
+                    Token::LessEqual => Expression::Leq(box lhs, box rhs),
+                    Token::MoreEqual => Expression::Geq(box lhs, box rhs),
+                    Token::Div => Expression::Div(box lhs, box rhs),
+                    Token::Mod => Expression::Mod(box lhs, box rhs),
+                    Token::LShift => Expression::LShift(box lhs, box rhs),
+                    Token::RShift => Expression::RShift(box lhs, box rhs),
+                    Token::Or => Expression::Or(box lhs, box rhs),
+                    Token::Xor => Expression::Xor(box lhs, box rhs),
+                    Token::And => Expression::And(box lhs, box rhs),
+                    Token::BAnd => Expression::BAnd(box lhs, box rhs),
+                    Token::BOr => Expression::BOr(box lhs, box rhs),
+                    _ => panic!("Unreachable! {:?} is not an operator", op),
+                }
+            };
         }
     }
 }
@@ -162,11 +183,15 @@ impl Default for Precedence {
 }
 
 impl Precedence {
+    fn is_right_associative(&self) -> bool {
+        *self == Precedence::Exp
+    }
+
     fn of(token: &Token) -> Option<Self> {
         use self::Precedence::*;
         match token {
             Token::Pipe => Some(Pipe),
-            Token::Question | Token::Colon => Some(Ternary),
+            Token::Question => Some(Ternary),
             Token::BOr => Some(BoolOr),
             Token::BAnd => Some(BoolAnd),
             Token::Equal | Token::NotEqual | Token::Less | Token::More | Token::LessEqual | Token::MoreEqual => Some(Comparison),
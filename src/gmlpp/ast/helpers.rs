@@ -2,20 +2,27 @@
 
 use super::super::tokenizer::{Token, Tokens};
 use super::fragment::Fragment;
+use super::statement::{Statement, StatementKind};
 use error::{Error, ParseError};
 
 pub fn semi_or_eol(tokens: &Tokens) -> Result<(), ParseError> {
     match tokens[..2] {
         [Token::Semi, Token::EOL] => tokens.skip(2),
         [Token::Semi, _] | [Token::EOL, _] => tokens.skip(1),
-        _ => {
-            eprintln!("Could not end statement: {:?}", tokens[..2].to_vec());
-            return Err(ParseError::ExpectedEndOfStatement)
-        },
+        // the last statement in a block can be ended by the closing brace, which is left for the block
+        [Token::RBrace, _] => {}
+        _ => return Err(ParseError::ExpectedEndOfStatement),
     }
     Ok(())
 }
 
+/// Skips over any line breaks, which are insignificant between statements
+pub fn skip_eol(tokens: &Tokens) {
+    while tokens.peek() == Token::EOL || tokens.peek() == Token::FallthroughComment {
+        tokens.skip(1);
+    }
+}
+
 pub fn parenthesized<T: Fragment>(tokens: &Tokens) -> Result<T, Error> {
     if tokens[0] != Token::LParen {
         return Err(Error::ParseError(ParseError::ExpectedParentheses));
@@ -28,3 +35,72 @@ pub fn parenthesized<T: Fragment>(tokens: &Tokens) -> Result<T, Error> {
     tokens.skip(1);
     Ok(thing)
 }
+
+/// Parses a statement. If it has a syntax error, the error is recorded and the rest of the statement
+/// is skipped so parsing can carry on, leaving a noop in its place
+pub fn recovering(tokens: &Tokens) -> Statement {
+    let start = tokens.pos();
+    tokens.reset_furthest();
+    match Statement::parse(tokens) {
+        Ok(statement) => statement,
+        Err(error) => {
+            tokens.report(tokens.locate(error));
+            tokens.retreat(start);
+            synchronize(tokens);
+            // a stray closing brace can't be skipped as part of a statement, so is skipped alone
+            if tokens.pos() == start && tokens.peek() != Token::EOF {
+                tokens.skip(1);
+            }
+            Statement::new(StatementKind::Noop, tokens.span_from(start))
+        }
+    }
+}
+
+/// Skips to the end of the current statement: past the next `;` or line break that is not nested in
+/// a block, or a block and any `else` after it, or up to the `}` closing the enclosing block. Brackets
+/// are not counted, as one left unclosed would otherwise skip the rest of the file
+fn synchronize(tokens: &Tokens) {
+    let mut depth = 0usize;
+    loop {
+        match tokens.peek() {
+            Token::EOF => return,
+            Token::RBrace if depth == 0 => return,
+            Token::Semi | Token::EOL if depth == 0 => {
+                tokens.skip(1);
+                return;
+            }
+            Token::LBrace => depth += 1,
+            Token::RBrace => {
+                depth -= 1;
+                if depth == 0 && tokens[1] != Token::Else {
+                    tokens.skip(1);
+                    return;
+                }
+            }
+            _ => {}
+        }
+        tokens.skip(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::AST;
+
+    #[test]
+    fn every_broken_statement_is_reported() {
+        let source = "x = 1 +\ny = 2\nz = )\nw = 3\n";
+        let ast = AST::from_reader(source.as_bytes()).unwrap();
+        let lines: Vec<_> = ast.errors().iter().map(|error| error.span().map(|span| span.start.line)).collect();
+        assert_eq!(lines, vec![Some(1), Some(3)]);
+    }
+
+    #[test]
+    fn reports_errors_on_consecutive_lines() {
+        let source = "var y = (2\nif (x { y = 2 }\nvar z = 3\n";
+        let ast = AST::from_reader(source.as_bytes()).unwrap();
+        assert_eq!(ast.errors().len(), 2);
+        assert_eq!(ast.errors()[0].span().map(|span| span.start.line), Some(1));
+        assert_eq!(ast.errors()[1].span().map(|span| span.start.line), Some(2));
+    }
+}
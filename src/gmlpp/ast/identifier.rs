@@ -1,11 +1,11 @@
 use std::fmt::{self, Display, Formatter};
 
 use super::fragment::Fragment;
-use super::super::tokenizer::{Token, Tokens};
+use super::super::tokenizer::{Token, Tokens, Span};
 use error::{Error, ParseError};
 
 #[derive(Clone, Debug)]
-pub struct Identifier(String);
+pub struct Identifier(pub String, pub Span);
 
 impl Display for Identifier {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
@@ -17,13 +17,11 @@ impl Fragment for Identifier {
     fn parse(tokens: &Tokens) -> Result<Self, Error> {
         match tokens.peek() {
             Token::Identifier(ident) => {
+                let span = tokens.span();
                 tokens.skip(1);
-                Ok(Identifier(ident))
-            }
-            _ => {
-                eprintln!("Failed to match Identifier: {:?}", tokens.peek());
-                Err(Error::ParseError(ParseError::ExpectedIdentifier))
+                Ok(Identifier(ident, span))
             }
+            _ => Err(Error::ParseError(ParseError::ExpectedIdentifier)),
         }
     }
 }
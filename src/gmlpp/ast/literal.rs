@@ -39,7 +39,6 @@ impl Fragment for Literal {
             Token::UndefinedLiteral => Ok(Literal::Undefined),
             _ => {
                 tokens.back(1);
-                eprintln!("Failed to match Literal: {:?}", tokens.peek());
                 Err(Error::ParseError(ParseError::ExpectedLiteral))
             }
         }
@@ -2,25 +2,121 @@ use std::fmt::{self, Display, Formatter};
 
 use super::fragment::Fragment;
 use super::identifier::Identifier;
-use super::super::tokenizer::{Token, Tokens};
+use super::comma_list::CommaList;
+use super::super::tokenizer::{Token, Tokens, Span};
 use error::{Error, ParseError};
 
-// TODO: other case
+/// A location which can be read from or written to
 #[derive(Clone, Debug)]
 pub enum LValue {
     Identifier(Identifier),
+    Global(Span),
+    Member(Box<LValue>, Identifier),
+    Index(Box<LValue>, Accessor, CommaList),
+}
+
+/// The kind of data structure being indexed by square brackets
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Accessor {
+    Array,
+    ArrayRef,
+    Map,
+    List,
+    Grid,
+}
+
+impl Accessor {
+    fn parse(tokens: &Tokens) -> Self {
+        let accessor = match tokens.peek() {
+            Token::At => Accessor::ArrayRef,
+            Token::Question => Accessor::Map,
+            Token::Or => Accessor::List,
+            Token::Hash => Accessor::Grid,
+            _ => return Accessor::Array,
+        };
+        tokens.skip(1);
+        accessor
+    }
+
+    /// Whether this accessor can be used with the given number of indices
+    fn accepts(&self, indices: usize) -> bool {
+        match self {
+            Accessor::Array | Accessor::ArrayRef => indices == 1 || indices == 2,
+            Accessor::Map | Accessor::List => indices == 1,
+            Accessor::Grid => indices == 2,
+        }
+    }
+}
+
+impl Display for Accessor {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Accessor::Array => Ok(()),
+            Accessor::ArrayRef => write!(f, "@ "),
+            Accessor::Map => write!(f, "? "),
+            Accessor::List => write!(f, "| "),
+            Accessor::Grid => write!(f, "# "),
+        }
+    }
+}
+
+impl LValue {
+    /// Where this lvalue was written in the source file. An index only covers what is indexed
+    pub fn span(&self) -> Span {
+        match self {
+            LValue::Identifier(ref ident) => ident.1,
+            LValue::Global(ref span) => *span,
+            LValue::Member(ref lvalue, ref ident) => lvalue.span().to(ident.1),
+            LValue::Index(ref lvalue, ..) => lvalue.span(),
+        }
+    }
 }
 
 impl Display for LValue {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
             LValue::Identifier(ref ident) => ident.fmt(f),
+            LValue::Global(..) => write!(f, "global"),
+            LValue::Member(ref lvalue, ref ident) => write!(f, "{}.{}", lvalue, ident),
+            LValue::Index(ref lvalue, ref accessor, ref indices) => write!(f, "{}[{}{}]", lvalue, accessor, indices),
         }
     }
 }
 
 impl Fragment for LValue {
     fn parse(tokens: &Tokens) -> Result<Self, Error> {
-        Ok(LValue::Identifier(Identifier::parse(tokens)?))
+        let mut lvalue = match tokens.peek() {
+            Token::Global => {
+                let span = tokens.span();
+                tokens.skip(1);
+                if tokens.peek() != Token::Dot {
+                    return Err(Error::ParseError(ParseError::ExpectedMember));
+                }
+                LValue::Global(span)
+            }
+            _ => LValue::Identifier(Identifier::parse(tokens)?),
+        };
+        loop {
+            match tokens.peek() {
+                Token::Dot => {
+                    tokens.skip(1);
+                    let member = Identifier::parse(tokens).map_err(|_| ParseError::ExpectedMember)?;
+                    lvalue = LValue::Member(box lvalue, member);
+                }
+                Token::LBrack => {
+                    tokens.skip(1);
+                    let accessor = Accessor::parse(tokens);
+                    let indices = CommaList::parse(tokens)?;
+                    if tokens.next() != Token::RBrack {
+                        return Err(Error::ParseError(ParseError::MismatchedBrackets));
+                    }
+                    if !accessor.accepts(indices.0.len()) {
+                        return Err(Error::ParseError(ParseError::WrongNumberOfIndices));
+                    }
+                    lvalue = LValue::Index(box lvalue, accessor, indices);
+                }
+                _ => return Ok(lvalue),
+            }
+        }
     }
 }
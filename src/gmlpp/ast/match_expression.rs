@@ -0,0 +1,56 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::arm::Arm;
+use super::expression::Expression;
+use super::fragment::Fragment;
+use super::helpers::skip_eol;
+use super::super::tokenizer::{Token, Tokens};
+use error::{Error, ParseError};
+
+/// A `match`, which can be used as either a statement or a value
+#[derive(Clone, Debug)]
+pub struct Match {
+    pub value: Box<Expression>,
+    pub arms: Vec<Arm>,
+}
+
+impl Display for Match {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let indent = f.precision().unwrap_or(0);
+        writeln!(f, "match {} {{", self.value)?;
+        for arm in &self.arms {
+            writeln!(f, "{0:1$}{2:.3$},", "", indent + 4, arm, indent + 4)?;
+        }
+        write!(f, "{0:1$}}}", "", indent)
+    }
+}
+
+impl Fragment for Match {
+    fn parse(tokens: &Tokens) -> Result<Self, Error> {
+        if tokens.next() != Token::Match {
+            tokens.back(1);
+            return Err(Error::ParseError(ParseError::ExpectedKeyword));
+        }
+        let value = Expression::parse(tokens)?;
+        skip_eol(tokens);
+        if tokens.next() != Token::LBrace {
+            return Err(Error::ParseError(ParseError::ExpectedBlock));
+        }
+        let mut arms = vec![];
+        loop {
+            skip_eol(tokens);
+            if tokens.peek() == Token::RBrace {
+                break;
+            }
+            arms.push(Arm::parse(tokens)?);
+            // arms can be separated by either commas or line breaks
+            match tokens.peek() {
+                Token::Comma | Token::EOL => tokens.skip(1),
+                Token::RBrace => break,
+                _ => return Err(Error::ParseError(ParseError::ExpectedComma)),
+            }
+        }
+        tokens.skip(1);
+        Ok(Match { value: box value, arms })
+    }
+}
@@ -13,6 +13,11 @@ mod lvalue;
 mod assignment;
 mod expression;
 mod statement;
+mod case;
+mod enum_member;
+mod match_expression;
+mod arm;
+mod ty;
 mod statements;
 mod value;
 mod literal;
@@ -20,22 +25,57 @@ mod call;
 mod doc_comment;
 mod helpers;
 
-use self::code::Code;
+pub use self::code::Code;
+pub use self::argument_list::ArgumentList;
+pub use self::comma_list::CommaList;
+pub use self::identifier::Identifier;
+pub use self::lvalue::{LValue, Accessor};
+pub use self::assignment::Assignment;
+pub use self::expression::Expression;
+pub use self::statement::{Statement, StatementKind};
+pub use self::case::Case;
+pub use self::match_expression::Match;
+pub use self::arm::{Arm, Pattern};
+pub use self::ty::Type;
+pub use self::value::Value;
+pub use self::literal::Literal;
+pub use self::call::Call;
 use self::fragment::Fragment;
 
-/// The abstract syntax tree of a .gmlpp program
+/// The abstract syntax tree of a .gmlpp program, the syntax errors found while parsing it, and whether
+/// it had comments which are not in the tree. When there are errors, the tree is missing the
+/// statements they were in
 #[derive(Debug)]
-pub struct AST(Code);
+pub struct AST(Code, Vec<Error>, bool);
 
 impl AST {
-    fn new(code: Code) -> Self {
-        AST(code)
-    }
 
     /// Creates an AST of an event by parsing a reader
     pub fn from_reader<R>(reader: R) -> Result<Self, Error> where R: Read + Send {
         let tokens = tokenizer::tokenize(reader)?;
-        Code::parse(&tokens).map(AST::new)
+        // the parser backtracks when it fails, so the error is wherever it got furthest before that
+        let code = Code::parse(&tokens).map_err(|error| tokens.locate(error))?;
+        Ok(AST(code, tokens.take_errors(), tokens.has_comments()))
+    }
+
+    /// The code this tree is made of
+    pub fn code(&self) -> &Code {
+        &self.0
+    }
+
+    /// The syntax errors in the source
+    pub fn errors(&self) -> &[Error] {
+        &self.1
+    }
+
+    /// Takes the syntax errors in the source, discarding the tree
+    pub fn into_errors(self) -> Vec<Error> {
+        self.1
+    }
+
+    /// Whether the source had comments which are not in the tree, so would be lost by printing it
+    pub fn has_comments(&self) -> bool {
+        self.2
     }
 
     /// Prints the GMLPP code this tree is encoding
@@ -43,3 +83,35 @@ impl AST {
         format!("{}", self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::AST;
+
+    fn parse(source: &str) -> AST {
+        AST::from_reader(source.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn doc_comments_are_printed() {
+        let source = "/// Adds one\nargument a;\n\nreturn a + 1;\n";
+        let ast = parse(source);
+        assert!(!ast.has_comments());
+        assert_eq!(ast.print(), source);
+    }
+
+    #[test]
+    fn comment_markers_in_strings_are_not_comments() {
+        assert!(!parse("url = \"http://example.com/*\"\n").has_comments());
+    }
+
+    #[test]
+    fn line_comments_are_noticed() {
+        assert!(parse("x = 1 // one\n").has_comments());
+    }
+
+    #[test]
+    fn fallthrough_comments_are_kept() {
+        assert!(!parse("switch x {\n    case 1:\n        y = 1\n        // fallthrough\n    case 2:\n        y = 2\n}\n").has_comments());
+    }
+}
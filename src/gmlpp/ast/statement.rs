@@ -3,21 +3,37 @@ use std::fmt::{self, Display, Formatter};
 use super::expression::Expression;
 use super::identifier::Identifier;
 use super::assignment::Assignment;
+use super::case::Case;
+use super::enum_member::EnumMember;
+use super::match_expression::Match;
+use super::value::Value;
+use super::literal::Literal;
+use super::ty::{Type, Annotation, annotation};
 use super::fragment::Fragment;
-use super::helpers::{semi_or_eol, parenthesized};
-use super::super::tokenizer::{Token, Tokens};
+use super::helpers::{semi_or_eol, skip_eol, parenthesized, recovering};
+use super::super::tokenizer::{Token, Tokens, Span};
 use error::{Error, ParseError};
 
+/// A statement, along with where it was written in the source file
 #[derive(Clone, Debug)]
-pub enum Statement {
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug)]
+pub enum StatementKind {
     Noop,
     Assignment(Assignment),
     Expression(Expression),
-    VarDecl(Identifier),
-    VarDeclAssign(Identifier, Expression),
+    VarDecl(Identifier, Option<Type>),
+    VarDeclAssign(Identifier, Option<Type>, Expression),
     GlobalvarDecl(Identifier),
     GlobalvarDeclAssign(Identifier, Expression),
-    EnumDecl(Identifier, Vec<()>), // TODO: enum cases
+    LetDecl(Identifier, Option<Type>),
+    LetDeclAssign(Identifier, Option<Type>, Expression),
+    ConstDecl(Identifier, Option<Type>, Expression),
+    EnumDecl(Identifier, Vec<EnumMember>),
     If(Expression, Box<Statement>, Option<Box<Statement>>),
     While(Expression, Box<Statement>),
     DoWhile(Box<Statement>, Expression),
@@ -25,8 +41,14 @@ pub enum Statement {
     DoUntil(Box<Statement>, Expression),
     Repeat(Expression, Box<Statement>),
     For(Box<Statement>, Expression, Box<Statement>, Box<Statement>),
+    ForIn(Identifier, Expression, Box<Statement>),
+    ForRange(Identifier, Expression, Expression, Box<Statement>),
+    ForOf(Identifier, Expression, Box<Statement>),
+    With(Expression, Box<Statement>),
+    Loop(Box<Statement>),
     Block(Vec<Statement>),
-    Switch(Box<Expression>, Vec<()>), // TODO: switch cases
+    Switch(Box<Expression>, Vec<Case>),
+    Match(Match),
     Return(Option<Expression>),
     Break,
     Exit,
@@ -34,50 +56,139 @@ pub enum Statement {
 }
 
 impl Statement {
+    pub fn new(kind: StatementKind, span: Span) -> Self {
+        Statement { kind, span }
+    }
+
     fn is_noop(&self) -> bool {
-        match self {
-            Statement::Noop => true,
+        match self.kind {
+            StatementKind::Noop => true,
             _ => false
         }
     }
 
     fn is_block(&self) -> bool {
-        match self {
-            Statement::Block(..) => true,
+        match self.kind {
+            StatementKind::Block(..) => true,
             _ => false
         }
     }
+
+    /// Whether this statement can be written in the header of a `for` loop
+    fn is_simple(&self) -> bool {
+        match self.kind {
+            StatementKind::Noop
+            | StatementKind::Assignment(..)
+            | StatementKind::Expression(..)
+            | StatementKind::VarDecl(..)
+            | StatementKind::VarDeclAssign(..)
+            | StatementKind::GlobalvarDecl(..)
+            | StatementKind::GlobalvarDeclAssign(..)
+            | StatementKind::LetDecl(..)
+            | StatementKind::LetDeclAssign(..)
+            | StatementKind::ConstDecl(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Parses a statement with no terminator, as in the update of a `for` loop
+    fn parse_simple(tokens: &Tokens) -> Result<Self, Error> {
+        let start = tokens.pos();
+        if tokens.peek() == Token::RParen {
+            return Ok(Statement::new(StatementKind::Noop, tokens.span()));
+        }
+        if let Ok(assignment) = Assignment::parse(tokens) {
+            return Ok(Statement::new(StatementKind::Assignment(assignment), tokens.span_from(start)));
+        }
+        tokens.seek(start);
+        let expression = Expression::parse(tokens)?;
+        Ok(Statement::new(StatementKind::Expression(expression), tokens.span_from(start)))
+    }
+
+    /// Parses the rest of a `for ... in` or `for ... of` loop, after the opening parenthesis
+    fn parse_iteration(tokens: &Tokens) -> Result<StatementKind, Error> {
+        let ident = Identifier::parse(tokens)?;
+        let of = tokens.next() == Token::Of;
+        let collection = Expression::parse(tokens)?;
+        let end = if !of && tokens.peek() == Token::DotDot {
+            tokens.skip(1);
+            Some(Expression::parse(tokens)?)
+        } else {
+            None
+        };
+        if tokens.next() != Token::RParen {
+            return Err(Error::ParseError(ParseError::MismatchedParentheses));
+        }
+        let body = box Statement::parse(tokens)?;
+        match end {
+            _ if of => Ok(StatementKind::ForOf(ident, collection, body)),
+            Some(end) => Ok(StatementKind::ForRange(ident, collection, end, body)),
+            None => Ok(StatementKind::ForIn(ident, collection, body)),
+        }
+    }
+
+    /// Writes a statement as it appears in the header of a `for` loop, without the semicolon
+    fn fmt_simple(&self, f: &mut Formatter) -> fmt::Result {
+        match self.kind {
+            StatementKind::Noop => Ok(()),
+            StatementKind::Assignment(ref assignment) => write!(f, "{}", assignment),
+            StatementKind::Expression(ref expression) => write!(f, "{}", expression),
+            StatementKind::VarDecl(ref ident, ref ty) => write!(f, "var {}{}", ident, Annotation(ty)),
+            StatementKind::VarDeclAssign(ref ident, ref ty, ref value) => write!(f, "var {}{} = {}", ident, Annotation(ty), value),
+            StatementKind::LetDecl(ref ident, ref ty) => write!(f, "let {}{}", ident, Annotation(ty)),
+            StatementKind::LetDeclAssign(ref ident, ref ty, ref value) => write!(f, "let {}{} = {}", ident, Annotation(ty), value),
+            StatementKind::ConstDecl(ref ident, ref ty, ref value) => write!(f, "const {}{} = {}", ident, Annotation(ty), value),
+            StatementKind::GlobalvarDecl(ref ident) => write!(f, "globalvar {}", ident),
+            StatementKind::GlobalvarDeclAssign(ref ident, ref value) => write!(f, "globalvar {} = {}", ident, value),
+            // the parser does not allow anything else here, but it is still written as well as it can be
+            _ => write!(f, "{}", self.to_string().trim_end().trim_end_matches(';')),
+        }
+    }
 }
 
 impl Display for Statement {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl Display for StatementKind {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let indent = f.precision().unwrap_or(0);
         match self {
-            Statement::Noop => writeln!(f, "{0:1$};", "", indent),
-            Statement::Block(ref statements) => {
+            StatementKind::Noop => writeln!(f, "{0:1$};", "", indent),
+            StatementKind::Block(ref statements) => {
                 writeln!(f, "{{")?;
                 for statement in statements {
-                    match statement {
+                    match statement.kind {
                         // Don't print noops in a block. There's no point
-                        Statement::Noop => continue,
-                        _ => writeln!(f, "{0:.1$}", statement, indent + 4)?,
+                        StatementKind::Noop => continue,
+                        // every other statement ends its own line, but a block is written where it goes
+                        StatementKind::Block(..) => writeln!(f, "{0:1$}{2:.1$}", "", indent + 4, statement)?,
+                        _ => write!(f, "{0:.1$}", statement, indent + 4)?,
                     }
                 }
                 write!(f, "{0:1$}}}", "", indent)
             }
-            Statement::Assignment(ref assignment) => writeln!(f, "{0:1$}{2};", "", indent, assignment),
-            Statement::Expression(ref expression) => writeln!(f, "{0:1$}{2};", "", indent, expression),
-            Statement::VarDecl(ref ident) => writeln!(f, "{0:1$}var {2};", "", indent, ident),
-            Statement::VarDeclAssign(ref ident, ref value) => writeln!(f, "{0:1$}var {2} = {3};", "", indent, ident, value),
-            Statement::GlobalvarDecl(ref ident) => writeln!(f, "{0:1$}globalvar {2};", "", indent, ident),
-            Statement::GlobalvarDeclAssign(ref ident, ref value) => writeln!(f, "{0:1$}globalvar {2} = {3};", "", indent, ident, value),
-            Statement::If(ref cond, ref body, None) if body.is_block() =>
+            StatementKind::Assignment(ref assignment) => writeln!(f, "{0:1$}{2};", "", indent, assignment),
+            StatementKind::Expression(ref expression) => writeln!(f, "{0:1$}{2};", "", indent, expression),
+            StatementKind::VarDecl(ref ident, ref ty) => writeln!(f, "{0:1$}var {2}{3};", "", indent, ident, Annotation(ty)),
+            StatementKind::VarDeclAssign(ref ident, ref ty, ref value) =>
+                writeln!(f, "{0:1$}var {2}{3} = {4};", "", indent, ident, Annotation(ty), value),
+            StatementKind::GlobalvarDecl(ref ident) => writeln!(f, "{0:1$}globalvar {2};", "", indent, ident),
+            StatementKind::GlobalvarDeclAssign(ref ident, ref value) => writeln!(f, "{0:1$}globalvar {2} = {3};", "", indent, ident, value),
+            StatementKind::LetDecl(ref ident, ref ty) => writeln!(f, "{0:1$}let {2}{3};", "", indent, ident, Annotation(ty)),
+            StatementKind::LetDeclAssign(ref ident, ref ty, ref value) =>
+                writeln!(f, "{0:1$}let {2}{3} = {4};", "", indent, ident, Annotation(ty), value),
+            StatementKind::ConstDecl(ref ident, ref ty, ref value) =>
+                writeln!(f, "{0:1$}const {2}{3} = {4};", "", indent, ident, Annotation(ty), value),
+            StatementKind::If(ref cond, ref body, None) if body.is_block() =>
                 writeln!(f, "{0:1$}if ({2}) {3:.1$}", "", indent, cond, body),
-            Statement::If(ref cond, ref body, None) => {
+            StatementKind::If(ref cond, ref body, None) => {
                 writeln!(f, "{0:1$}if ({2})", "", indent, cond)?;
                 writeln!(f, "{0:.1$}", body, indent + 4)
             }
-            Statement::If(ref cond, ref body, Some(ref fbody)) => {
+            StatementKind::If(ref cond, ref body, Some(ref fbody)) => {
                 if body.is_block() {
                     write!(f, "{0:1$}if ({2}) {3:.1$} ", "", indent, cond, body)?;
                 } else {
@@ -92,61 +203,151 @@ impl Display for Statement {
                 }
                 Ok(())
             }
-            Statement::While(ref cond, box Statement::Noop) =>
+            StatementKind::While(ref cond, ref body) if body.is_noop() =>
                 writeln!(f, "{0:1$}while ({2});", "", indent, cond),
-            Statement::While(ref cond, ref body) if body.is_block() =>
+            StatementKind::While(ref cond, ref body) if body.is_block() =>
                 writeln!(f, "{0:1$}while ({2}) {3:.1$}", "", indent, cond, body),
-            Statement::While(ref cond, ref body) => {
+            StatementKind::While(ref cond, ref body) => {
                 writeln!(f, "{0:1$}while ({2})", "", indent, cond)?;
                 writeln!(f, "{0:.1$}", body, indent)
             }
-            Statement::Until(ref cond, box Statement::Noop) =>
+            StatementKind::Until(ref cond, ref body) if body.is_noop() =>
                 writeln!(f, "{0:1$}until ({2});", "", indent, cond),
-            Statement::Until(ref cond, ref body) if body.is_block() =>
+            StatementKind::Until(ref cond, ref body) if body.is_block() =>
                 writeln!(f, "{0:1$}until ({2}) {3:.1$}", "", indent, cond, body),
-            Statement::Until(ref cond, ref body) => {
+            StatementKind::Until(ref cond, ref body) => {
                 writeln!(f, "{0:1$}until ({2})", "", indent, cond)?;
                 writeln!(f, "{0:.1$}", body, indent)
             }
-            Statement::DoUntil(box Statement::Noop, ref cond) =>
+            StatementKind::DoUntil(ref body, ref cond) if body.is_noop() =>
                 writeln!(f, "{0:1$}do; until ({2});", "", indent, cond),
-            Statement::DoUntil(ref body, ref cond) if body.is_block() =>
+            StatementKind::DoUntil(ref body, ref cond) if body.is_block() =>
                 writeln!(f, "{0:1$}do {3:.1$} until ({2})", "", indent, cond, body),
-            Statement::DoUntil(ref body, ref cond) => {
+            StatementKind::DoUntil(ref body, ref cond) => {
                 writeln!(f, "{0:1$}do", "", indent)?;
-                writeln!(f, "{0:.1$}", body, indent)?;
+                writeln!(f, "{0:.1$}", body, indent + 4)?;
                 writeln!(f, "{0:1$}until ({2})", "", indent, cond)
             }
-            _ => unimplemented!(),
+            StatementKind::DoWhile(ref body, ref cond) if body.is_block() =>
+                writeln!(f, "{0:1$}do {3:.1$} while ({2})", "", indent, cond, body),
+            StatementKind::DoWhile(ref body, ref cond) => {
+                writeln!(f, "{0:1$}do", "", indent)?;
+                writeln!(f, "{0:.1$}", body, indent + 4)?;
+                writeln!(f, "{0:1$}while ({2})", "", indent, cond)
+            }
+            StatementKind::Repeat(ref times, ref body) if body.is_block() =>
+                writeln!(f, "{0:1$}repeat ({2}) {3:.1$}", "", indent, times, body),
+            StatementKind::Repeat(ref times, ref body) => {
+                writeln!(f, "{0:1$}repeat ({2})", "", indent, times)?;
+                writeln!(f, "{0:.1$}", body, indent + 4)
+            }
+            StatementKind::For(ref init, ref cond, ref update, ref body) => {
+                write!(f, "{0:1$}for (", "", indent)?;
+                init.fmt_simple(f)?;
+                write!(f, "; {}; ", cond)?;
+                update.fmt_simple(f)?;
+                if body.is_block() {
+                    writeln!(f, ") {0:.1$}", body, indent)
+                } else {
+                    writeln!(f, ")")?;
+                    writeln!(f, "{0:.1$}", body, indent + 4)
+                }
+            }
+            StatementKind::ForIn(ref ident, ref collection, ref body) if body.is_block() =>
+                writeln!(f, "{0:1$}for ({2} in {3}) {4:.1$}", "", indent, ident, collection, body),
+            StatementKind::ForIn(ref ident, ref collection, ref body) => {
+                writeln!(f, "{0:1$}for ({2} in {3})", "", indent, ident, collection)?;
+                writeln!(f, "{0:.1$}", body, indent + 4)
+            }
+            StatementKind::ForRange(ref ident, ref start, ref end, ref body) if body.is_block() =>
+                writeln!(f, "{0:1$}for ({2} in {3}..{4}) {5:.1$}", "", indent, ident, start, end, body),
+            StatementKind::ForRange(ref ident, ref start, ref end, ref body) => {
+                writeln!(f, "{0:1$}for ({2} in {3}..{4})", "", indent, ident, start, end)?;
+                writeln!(f, "{0:.1$}", body, indent + 4)
+            }
+            StatementKind::ForOf(ref ident, ref collection, ref body) if body.is_block() =>
+                writeln!(f, "{0:1$}for ({2} of {3}) {4:.1$}", "", indent, ident, collection, body),
+            StatementKind::ForOf(ref ident, ref collection, ref body) => {
+                writeln!(f, "{0:1$}for ({2} of {3})", "", indent, ident, collection)?;
+                writeln!(f, "{0:.1$}", body, indent + 4)
+            }
+            StatementKind::With(ref target, ref body) if body.is_block() =>
+                writeln!(f, "{0:1$}with ({2}) {3:.1$}", "", indent, target, body),
+            StatementKind::With(ref target, ref body) => {
+                writeln!(f, "{0:1$}with ({2})", "", indent, target)?;
+                writeln!(f, "{0:.1$}", body, indent + 4)
+            }
+            StatementKind::Loop(ref body) if body.is_block() =>
+                writeln!(f, "{0:1$}loop {2:.1$}", "", indent, body),
+            StatementKind::Loop(ref body) => {
+                writeln!(f, "{0:1$}loop", "", indent)?;
+                writeln!(f, "{0:.1$}", body, indent + 4)
+            }
+            StatementKind::EnumDecl(ref name, ref members) => {
+                writeln!(f, "{0:1$}enum {2} {{", "", indent, name)?;
+                for member in members {
+                    writeln!(f, "{0:1$}{2},", "", indent + 4, member)?;
+                }
+                writeln!(f, "{0:1$}}}", "", indent)
+            }
+            StatementKind::Switch(ref value, ref cases) => {
+                writeln!(f, "{0:1$}switch ({2}) {{", "", indent, value)?;
+                for case in cases {
+                    write!(f, "{0:.1$}", case, indent + 4)?;
+                }
+                writeln!(f, "{0:1$}}}", "", indent)
+            }
+            StatementKind::Match(ref expr) => writeln!(f, "{0:1$}{2:.1$}", "", indent, expr),
+            StatementKind::Return(None) => writeln!(f, "{0:1$}return;", "", indent),
+            StatementKind::Return(Some(ref value)) => writeln!(f, "{0:1$}return {2};", "", indent, value),
+            StatementKind::Break => writeln!(f, "{0:1$}break;", "", indent),
+            StatementKind::Exit => writeln!(f, "{0:1$}exit;", "", indent),
+            StatementKind::Continue => writeln!(f, "{0:1$}continue;", "", indent),
         }
     }
 }
 
 impl Fragment for Statement {
+    fn parse(tokens: &Tokens) -> Result<Self, Error> {
+        skip_eol(tokens);
+        let start = tokens.pos();
+        let kind = StatementKind::parse(tokens)?;
+        Ok(Statement::new(kind, tokens.span_from(start)))
+    }
+}
+
+impl StatementKind {
     fn parse(tokens: &Tokens) -> Result<Self, Error> {
         match tokens[..1] {
             [Token::Semi] => {
                 semi_or_eol(tokens)?;
-                Ok(Statement::Noop)
+                Ok(StatementKind::Noop)
             }
-            [Token::Var] | [Token::Globalvar] => {
+            [Token::Var] | [Token::Globalvar] | [Token::Let] | [Token::Const] => {
                 let var_type = tokens.next();
                 let ident = Identifier::parse(tokens)?;
+                // globalvars cannot be given a type, as they could be assigned from anywhere
+                let ty = if var_type == Token::Globalvar { None } else { annotation(tokens)? };
                 if tokens.peek() == Token::Assign {
                     tokens.skip(1);
                     let value = Expression::parse(tokens)?;
                     semi_or_eol(tokens)?;
-                    if var_type == Token::Var {
-                        Ok(Statement::VarDeclAssign(ident, value))
-                    } else {
-                        Ok(Statement::GlobalvarDeclAssign(ident, value))
+                    match var_type {
+                        Token::Var => Ok(StatementKind::VarDeclAssign(ident, ty, value)),
+                        Token::Globalvar => Ok(StatementKind::GlobalvarDeclAssign(ident, value)),
+                        Token::Let => Ok(StatementKind::LetDeclAssign(ident, ty, value)),
+                        _ => Ok(StatementKind::ConstDecl(ident, ty, value)),
                     }
                 } else {
+                    // a constant could never be given a value later
+                    if var_type == Token::Const {
+                        return Err(Error::ParseError(ParseError::ExpectedAssignment));
+                    }
                     semi_or_eol(tokens)?;
-                    if var_type == Token::Var {
-                        Ok(Statement::VarDecl(ident))
-                    } else {
-                        Ok(Statement::GlobalvarDecl(ident))
+                    match var_type {
+                        Token::Var => Ok(StatementKind::VarDecl(ident, ty)),
+                        Token::Globalvar => Ok(StatementKind::GlobalvarDecl(ident)),
+                        _ => Ok(StatementKind::LetDecl(ident, ty)),
                     }
                 }
             }
@@ -154,112 +355,224 @@ impl Fragment for Statement {
                 tokens.skip(1);
                 let cond = parenthesized::<Expression>(tokens)?;
                 let body = Statement::parse(tokens)?;
+                skip_eol(tokens);
                 if tokens.peek() == Token::Else {
                     tokens.skip(1);
                     let fbody = Statement::parse(tokens)?;
-                    Ok(Statement::If(cond, box body, Some(box fbody)))
+                    Ok(StatementKind::If(cond, box body, Some(box fbody)))
                 } else {
-                    Ok(Statement::If(cond, box body, None))
+                    Ok(StatementKind::If(cond, box body, None))
                 }
             }
             [Token::Do] => {
                 tokens.skip(1);
                 let body = Statement::parse(tokens)?;
+                skip_eol(tokens);
                 let loop_type = tokens.next();
                 if loop_type != Token::While && loop_type != Token::Until {
                     return Err(Error::ParseError(ParseError::ExpectedKeyword));
                 }
                 let cond = parenthesized::<Expression>(tokens)?;
                 if loop_type == Token::While {
-                    Ok(Statement::DoWhile(box body, cond))
+                    Ok(StatementKind::DoWhile(box body, cond))
                 } else {
-                    Ok(Statement::DoUntil(box body, cond))
+                    Ok(StatementKind::DoUntil(box body, cond))
                 }
             }
-            // for loop is super sketchy. probably will not be so working
             [Token::For] => {
                 tokens.skip(1);
-                if tokens[0] != Token::LParen {
+                if tokens.next() != Token::LParen {
                     return Err(Error::ParseError(ParseError::ExpectedParentheses));
                 }
+                match tokens[..2] {
+                    [Token::Identifier(..), Token::In] | [Token::Identifier(..), Token::Of] => return Statement::parse_iteration(tokens),
+                    _ => {}
+                }
+                // the initializer consumes its own semicolon
+                let start = tokens.pos();
                 let init = Statement::parse(tokens)?;
-                let cond = Expression::parse(tokens)?;
-                let update = Statement::parse(tokens)?;
-                if tokens.peek() != Token::RParen {
+                if !init.is_simple() {
+                    return Err(Error::ParseError(ParseError::ExpectedLoopInitializer).at(tokens.span_from(start)));
+                }
+                // an empty condition loops forever
+                let cond = if tokens.peek() == Token::Semi {
+                    Expression::Value(Value::Literal(Literal::Boolean(true)), tokens.span())
+                } else {
+                    Expression::parse(tokens)?
+                };
+                if tokens.next() != Token::Semi {
+                    return Err(Error::ParseError(ParseError::ExpectedEndOfStatement));
+                }
+                let update = Statement::parse_simple(tokens)?;
+                if tokens.next() != Token::RParen {
                     return Err(Error::ParseError(ParseError::MismatchedParentheses));
                 }
                 let body = Statement::parse(tokens)?;
-                Ok(Statement::For(box init, cond, box update, box body))
+                Ok(StatementKind::For(box init, cond, box update, box body))
+            }
+            [Token::With] => {
+                tokens.skip(1);
+                let target = parenthesized::<Expression>(tokens)?;
+                let body = Statement::parse(tokens)?;
+                Ok(StatementKind::With(target, box body))
+            }
+            [Token::Loop] => {
+                tokens.skip(1);
+                let body = Statement::parse(tokens)?;
+                Ok(StatementKind::Loop(box body))
             }
             [Token::Repeat] => {
                 tokens.skip(1);
                 let times = parenthesized::<Expression>(tokens)?;
                 let body = Statement::parse(tokens)?;
-                Ok(Statement::Repeat(times, box body))
+                Ok(StatementKind::Repeat(times, box body))
             }
             [Token::While] | [Token::Until] => {
                 let loop_type = tokens.next();
                 let cond = parenthesized::<Expression>(tokens)?;
                 let body = Statement::parse(tokens)?;
                 if loop_type == Token::While {
-                    Ok(Statement::While(cond, box body))
+                    Ok(StatementKind::While(cond, box body))
                 } else {
-                    Ok(Statement::Until(cond, box body))
+                    Ok(StatementKind::Until(cond, box body))
                 }
             }
             [Token::Break] => {
                 tokens.skip(1);
                 semi_or_eol(tokens)?;
-                Ok(Statement::Break)
+                Ok(StatementKind::Break)
             }
             [Token::Continue] => {
                 tokens.skip(1);
                 semi_or_eol(tokens)?;
-                Ok(Statement::Continue)
+                Ok(StatementKind::Continue)
             }
             [Token::Exit] => {
                 tokens.skip(1);
                 semi_or_eol(tokens)?;
-                Ok(Statement::Exit)
+                Ok(StatementKind::Exit)
             }
             [Token::Return] => {
                 tokens.skip(1);
                 match semi_or_eol(tokens) {
-                    Ok(..) => Ok(Statement::Return(None)),
+                    Ok(..) => Ok(StatementKind::Return(None)),
                     Err(_) => {
                         let value = Expression::parse(tokens)?;
                         semi_or_eol(tokens)?;
-                        Ok(Statement::Return(Some(value)))
+                        Ok(StatementKind::Return(Some(value)))
                     }
                 }
             }
-            [Token::LBrace] => {
-                let mut statements = Vec::new();
+            [Token::Enum] => {
                 tokens.skip(1);
+                let name = Identifier::parse(tokens)?;
+                skip_eol(tokens);
+                if tokens.next() != Token::LBrace {
+                    return Err(Error::ParseError(ParseError::ExpectedBlock));
+                }
+                let mut members = Vec::new();
+                loop {
+                    skip_eol(tokens);
+                    if tokens.peek() == Token::RBrace {
+                        break;
+                    }
+                    members.push(EnumMember::parse(tokens)?);
+                    skip_eol(tokens);
+                    match tokens.peek() {
+                        Token::Comma => tokens.skip(1),
+                        Token::RBrace => break,
+                        _ => return Err(Error::ParseError(ParseError::ExpectedComma)),
+                    }
+                }
+                tokens.skip(1);
+                Ok(StatementKind::EnumDecl(name, members))
+            }
+            [Token::Switch] => {
+                tokens.skip(1);
+                let value = parenthesized::<Expression>(tokens)?;
+                skip_eol(tokens);
+                if tokens.next() != Token::LBrace {
+                    return Err(Error::ParseError(ParseError::ExpectedBlock));
+                }
+                let mut cases = Vec::new();
+                skip_eol(tokens);
                 while tokens.peek() != Token::RBrace {
-                    statements.push(Statement::parse(tokens)?);
+                    cases.push(Case::parse(tokens)?);
                 }
                 tokens.skip(1);
-                Ok(Statement::Block(statements))
+                Ok(StatementKind::Switch(box value, cases))
+            }
+            [Token::Match] => Ok(StatementKind::Match(Match::parse(tokens)?)),
+            [Token::LBrace] => {
+                let mut statements = Vec::new();
+                tokens.skip(1);
+                skip_eol(tokens);
+                while tokens.peek() != Token::RBrace && tokens.peek() != Token::EOF {
+                    statements.push(recovering(tokens));
+                    skip_eol(tokens);
+                }
+                if tokens.next() != Token::RBrace {
+                    return Err(Error::UnexpectedEOF);
+                }
+                Ok(StatementKind::Block(statements))
             }
             _ => {
                 let start = tokens.pos();
                 // try for expression
                 if let Ok(expr) = Expression::parse(tokens) {
                     if let Ok(()) = semi_or_eol(tokens) {
-                        return Ok(Statement::Expression(expr));
+                        return Ok(StatementKind::Expression(expr));
                     }
                 }
                 tokens.seek(start);
                 // try for assign
                 if let Ok(assg) = Assignment::parse(tokens) {
                     if let Ok(()) = semi_or_eol(tokens) {
-                        return Ok(Statement::Assignment(assg));
+                        return Ok(StatementKind::Assignment(assg));
                     }
                 }
-                eprintln!("Failed to match Statement. {:?}", tokens[..1].to_vec());
                 Err(Error::ParseError(ParseError::ExpectedStatement))
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::AST;
+
+    #[test]
+    fn every_loop_and_jump_can_be_parsed_and_printed() {
+        let source = concat!(
+            "for (var i = 0; i < 10; i += 1) {\n",
+            "    if (i == 5) {\n",
+            "        continue\n",
+            "    }\n",
+            "    repeat (3) {\n",
+            "        break\n",
+            "    }\n",
+            "}\n",
+            "with (obj_wall) {\n",
+            "    exit\n",
+            "}\n",
+            "loop {\n",
+            "    do {\n",
+            "        x += 1\n",
+            "    } while (x < 3)\n",
+            "    return x\n",
+            "}\n",
+        );
+        let printed = AST::from_reader(source.as_bytes()).unwrap().print();
+        for line in &["for (var i = 0; i < 10; i += 1) {", "repeat (3) {", "with (obj_wall) {", "loop {", "} while (x < 3)", "continue;", "break;", "exit;", "return x;"] {
+            assert!(printed.contains(line), "`{}` is not in {}", line, printed);
+        }
+    }
+
+    #[test]
+    fn body_of_a_do_loop_without_a_block_is_indented() {
+        let until = AST::from_reader("do x += 1\nuntil (x > 3)\n".as_bytes()).unwrap().print();
+        let while_ = AST::from_reader("do x += 1\nwhile (x > 3)\n".as_bytes()).unwrap().print();
+        assert!(until.contains("\n    x += 1;\n"), "the body is not indented in {}", until);
+        assert_eq!(until.replace("until", "while"), while_);
+    }
+}
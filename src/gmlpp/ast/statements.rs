@@ -1,21 +1,23 @@
 use std::fmt::{self, Display, Formatter};
 
-use super::statement::Statement;
+use super::statement::{Statement, StatementKind};
 use super::fragment::Fragment;
+use super::helpers::{skip_eol, recovering};
 use super::super::tokenizer::{Token, Tokens};
 use error::Error;
 
 /// The top level list of statements
 #[derive(Clone, Debug)]
-pub struct Statements(Vec<Statement>);
+pub struct Statements(pub Vec<Statement>);
 
 impl Display for Statements {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         for statement in &self.0 {
-            match statement {
+            match statement.kind {
                 // don't need to print noops at the top level
-                Statement::Noop => continue,
-                _ => writeln!(f, "{0:.0}", statement)?,
+                StatementKind::Noop => continue,
+                StatementKind::Block(..) => writeln!(f, "{0:.0}", statement)?,
+                _ => write!(f, "{0:.0}", statement)?,
             }
         }
         Ok(())
@@ -25,8 +27,10 @@ impl Display for Statements {
 impl Fragment for Statements {
     fn parse(tokens: &Tokens) -> Result<Self, Error> {
         let mut statements = Vec::new();
+        skip_eol(tokens);
         while tokens[0] != Token::EOF {
-            statements.push(Statement::parse(tokens)?);
+            statements.push(recovering(tokens));
+            skip_eol(tokens);
         }
         Ok(Statements(statements))
     }
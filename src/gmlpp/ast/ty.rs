@@ -0,0 +1,108 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::fragment::Fragment;
+use super::super::tokenizer::{Token, Tokens};
+use error::{Error, ParseError};
+
+/// A type annotation. Types only exist at compile time, and are erased from the GML
+#[derive(Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Type {
+    Bool,
+    Number,
+    String,
+    Char,
+    Array,
+    Symbol,
+    Void,
+    Null,
+    Never,
+    Map,
+    List,
+    Grid,
+    Object,
+    Room,
+    Sprite,
+    Script,
+    Path,
+    Tileset,
+    Sound,
+    Font,
+    Timeline,
+}
+
+impl Type {
+    /// Whether a value of the other type can be used where this type is expected
+    pub fn accepts(&self, other: Type) -> bool {
+        use self::Type::*;
+        match (*self, other) {
+            (expected, found) if expected == found => true,
+            // a value that never exists can be anything
+            (_, Never) => true,
+            // GML booleans are just numbers
+            (Number, Bool) | (Bool, Number) => true,
+            // characters are written out as strings
+            (String, Char) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Fragment for Type {
+    fn parse(tokens: &Tokens) -> Result<Self, Error> {
+        let ty = match tokens.next() {
+            Token::TBool => Type::Bool,
+            Token::TNumber => Type::Number,
+            Token::TString => Type::String,
+            Token::TChar => Type::Char,
+            Token::TArray => Type::Array,
+            Token::TSymbol => Type::Symbol,
+            Token::TVoid => Type::Void,
+            Token::TNull => Type::Null,
+            Token::TNever => Type::Never,
+            Token::TMap => Type::Map,
+            Token::TList => Type::List,
+            Token::TGrid => Type::Grid,
+            Token::TObject => Type::Object,
+            Token::TRoom => Type::Room,
+            Token::TSprite => Type::Sprite,
+            Token::TScript => Type::Script,
+            Token::TPath => Type::Path,
+            Token::TTileset => Type::Tileset,
+            Token::TSound => Type::Sound,
+            Token::TFont => Type::Font,
+            Token::TTimeline => Type::Timeline,
+            _ => {
+                tokens.back(1);
+                return Err(Error::ParseError(ParseError::ExpectedType));
+            }
+        };
+        Ok(ty)
+    }
+}
+
+/// Parses an optional `: Type` annotation
+pub fn annotation(tokens: &Tokens) -> Result<Option<Type>, Error> {
+    if tokens.peek() != Token::Colon {
+        return Ok(None);
+    }
+    tokens.skip(1);
+    Ok(Some(Type::parse(tokens)?))
+}
+
+/// Displays an optional annotation as it appears after a name
+pub struct Annotation<'a>(pub &'a Option<Type>);
+
+impl<'a> Display for Annotation<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.0 {
+            Some(ty) => write!(f, ": {}", ty),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,35 +1,48 @@
 use std::fmt::{self, Display, Formatter};
 
 use super::fragment::Fragment;
-use super::identifier::Identifier;
+use super::lvalue::LValue;
 use super::literal::Literal;
 use super::expression::Expression;
 use super::call::Call;
+use super::match_expression::Match;
 use super::super::tokenizer::{Token, Tokens};
 use error::{Error, ParseError};
 
 #[derive(Clone, Debug)]
 pub enum Value {
-    Ident(Identifier),
+    LValue(LValue),
     Literal(Literal),
     Expr(Box<Expression>),
     Call(Call),
+    Match(Match),
     Negative(Box<Value>),
     Not(Box<Value>),
     Inverted(Box<Value>),
+    PreIncrement(LValue),
+    PreDecrement(LValue),
+    PostIncrement(LValue),
+    PostDecrement(LValue),
+    Placeholder,
 }
 
 impl Display for Value {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         use self::Value::*;
         match self {
-            Ident(ref ident) => ident.fmt(f),
+            LValue(ref lvalue) => lvalue.fmt(f),
             Literal(ref literal) => literal.fmt(f),
             Expr(ref expr) => write!(f, "({})", expr),
             Call(ref call) => call.fmt(f),
+            Match(ref expr) => expr.fmt(f),
             Negative(ref inner) => write!(f, "-{}", inner),
             Not(ref inner) => write!(f, "!{}", inner),
             Inverted(ref inner) => write!(f, "~{}", inner),
+            PreIncrement(ref lvalue) => write!(f, "++{}", lvalue),
+            PreDecrement(ref lvalue) => write!(f, "--{}", lvalue),
+            PostIncrement(ref lvalue) => write!(f, "{}++", lvalue),
+            PostDecrement(ref lvalue) => write!(f, "{}--", lvalue),
+            Placeholder => write!(f, "_"),
         }
     }
 }
@@ -38,7 +51,20 @@ impl Fragment for Value {
     fn parse(tokens: &Tokens) -> Result<Self, Error> {
         match tokens[..2] {
             [Token::Identifier(..), Token::LParen] => Ok(Value::Call(Call::parse(tokens)?)),
-            [Token::Identifier(..), _] => Ok(Value::Ident(Identifier::parse(tokens)?)),
+            [Token::Identifier(..), _] | [Token::Global, _] => {
+                let lvalue = LValue::parse(tokens)?;
+                match tokens.peek() {
+                    Token::PlusPlus => {
+                        tokens.skip(1);
+                        Ok(Value::PostIncrement(lvalue))
+                    }
+                    Token::MinusMinus => {
+                        tokens.skip(1);
+                        Ok(Value::PostDecrement(lvalue))
+                    }
+                    _ => Ok(Value::LValue(lvalue)),
+                }
+            }
             [Token::BinLiteral(..), _] |
             [Token::DecLiteral(..), _] |
             [Token::HexLiteral(..), _] |
@@ -53,6 +79,7 @@ impl Fragment for Value {
                 if tokens.peek() != Token::RParen {
                     Err(Error::ParseError(ParseError::MismatchedParentheses))
                 } else {
+                    tokens.skip(1);
                     Ok(Value::Expr(box expr))
                 }
             }
@@ -68,10 +95,20 @@ impl Fragment for Value {
                 tokens.skip(1);
                 Ok(Value::Inverted(box Value::parse(tokens)?))
             }
-            _ => {
-                eprintln!("Failed to match Value: {:?}", tokens.peek());
-                Err(Error::ParseError(ParseError::ExpectedValue))
+            [Token::PlusPlus, _] => {
+                tokens.skip(1);
+                Ok(Value::PreIncrement(LValue::parse(tokens)?))
+            }
+            [Token::MinusMinus, _] => {
+                tokens.skip(1);
+                Ok(Value::PreDecrement(LValue::parse(tokens)?))
+            }
+            [Token::Match, _] => Ok(Value::Match(Match::parse(tokens)?)),
+            [Token::Underscore, _] => {
+                tokens.skip(1);
+                Ok(Value::Placeholder)
             }
+            _ => Err(Error::ParseError(ParseError::ExpectedValue)),
         }
     }
 }
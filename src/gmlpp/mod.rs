@@ -1,4 +1,5 @@
-mod ast;
+pub mod ast;
 mod tokenizer;
 
 pub use self::ast::AST;
+pub use self::tokenizer::{Span, Token};
@@ -5,9 +5,11 @@ use error::Error;
 mod token;
 mod state;
 mod tokens;
+mod span;
 
 pub use self::token::Token;
 pub use self::tokens::Tokens;
+pub use self::span::{Position, Span};
 use self::state::State;
 
 pub fn tokenize<R>(reader: R) -> Result<Tokens, Error>
@@ -15,45 +17,85 @@ where R: Read {
     let chars = reader.chars();
     let mut state = State::default();
     let mut token = "".to_owned();
-    let mut tokens = vec![Token::BOF];
+    let mut tokens = vec![(Token::BOF, Span::default())];
+    // where the next character is, and where the token being read started
+    let mut position = Position::default();
+    let mut start = position;
     for ch in chars {
         let c = ch?;
-        match state.next(c)? {
+        let here = Span::new(position, position.after(c));
+        match state.next(c).map_err(|error| error.at(here))? {
             Some(next) => {
+                if state == State::default() {
+                    start = position;
+                }
                 state = next;
                 if state != State::default() {
                     token.push(c);
                 }
             }
             None => {
-                tokens.push(Token::new(state, token.clone()));
+                tokens.push((Token::new(state, token.clone()), Span::new(start, position)));
                 token.clear();
-                state = State::default().next(c)?.unwrap();
+                start = position;
+                state = State::default().next(c).map_err(|error| error.at(here))?.unwrap();
                 if state != State::default() {
                     token = c.to_string();
                 }
             }
         }
+        position = position.after(c);
     }
     if state != State::default() {
         match state.next('\n')? {
-            None | Some(State::EOL) => tokens.push(Token::new(state, token)),
-            Some(_) => return Err(Error::UnexpectedEOF),
+            None | Some(State::EOL) => tokens.push((Token::new(state, token), Span::new(start, position))),
+            Some(_) => return Err(Error::UnexpectedEOF.at(Span::new(position, position))),
         }
     }
-    tokens.push(Token::EOF);
-    println!("{:?}", tokens);
-    Ok(Tokens::new(
-        tokens
-            .into_iter()
-            // Remove all comments because they're dumb
-            .filter(|token|
-                if let Token::Comment(..) = token {
-                    false
+    tokens.push((Token::EOF, Span::new(position, position)));
+    let mut filtered = Vec::with_capacity(tokens.len());
+    let mut spans = Vec::with_capacity(tokens.len());
+    let mut fallthrough = false;
+    let mut comments = false;
+    for (token, span) in tokens {
+        match token {
+            // Fallthrough comments are kept, but moved past the end of their line so they don't
+            // get in the way of ending the statement they follow
+            Token::Comment(..) if token.is_fallthrough_comment() => fallthrough = true,
+            // Remove all other comments because they're dumb
+            Token::Comment(..) => comments = true,
+            Token::EOL | Token::EOF if fallthrough => {
+                fallthrough = false;
+                if token == Token::EOL {
+                    filtered.push(token);
+                    filtered.push(Token::FallthroughComment);
                 } else {
-                    true
+                    filtered.push(Token::FallthroughComment);
+                    filtered.push(token);
                 }
-            )
-            .collect()
-    ))
+            }
+            _ if split_range(&mut filtered, &token) => {}
+            _ => filtered.push(token),
+        }
+        // anything split out of or moved past this token is given its span
+        spans.resize(filtered.len(), span);
+    }
+    Ok(Tokens::new(filtered, spans, comments))
+}
+
+/// Numbers are read greedily, so a range like `0..10` comes out as the numbers `0.` and `.10`. This
+/// splits the `..` back out of them, returning whether the token was the end of such a number
+fn split_range(tokens: &mut Vec<Token>, token: &Token) -> bool {
+    let rest = match token {
+        Token::Dot => None,
+        Token::DecLiteral(ref number) if number.starts_with('.') => Some(number[1..].to_owned()),
+        _ => return false,
+    };
+    match tokens.last_mut() {
+        Some(Token::DecLiteral(ref mut number)) if number.ends_with('.') => { number.pop(); }
+        _ => return false,
+    }
+    tokens.push(Token::DotDot);
+    tokens.extend(rest.map(Token::DecLiteral));
+    true
 }
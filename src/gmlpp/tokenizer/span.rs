@@ -0,0 +1,56 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A place in a source file. Lines and columns both count from 1
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// The position after a character
+    pub fn after(self, c: char) -> Self {
+        if c == '\n' {
+            Position { line: self.line + 1, column: 1 }
+        } else {
+            Position { line: self.line, column: self.column + 1 }
+        }
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Position { line: 1, column: 1 }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// The part of a source file some code was written in, from its start up to but not including its
+/// end
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Span { start, end }
+    }
+
+    /// The span covering both this and another span which comes after it
+    pub fn to(self, other: Span) -> Self {
+        Span { start: self.start, end: other.end }
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.start.fmt(f)
+    }
+}
@@ -82,6 +82,7 @@ pub enum State {
     At,
     Underscore,
     BarMore,
+    EqualMore,
     Dot,
     Comma,
     Semi,
@@ -163,7 +164,7 @@ impl State {
                     c if c.is_whitespace() => Ok(Some(Start)),
                     c if c.is_digit(10) => Ok(Some(Dec)),
                     c if c.is_alphabetic() => Ok(Some(Identifier)),
-                    _ => Err(Error::InvalidCharacter),
+                    _ => Err(Error::InvalidCharacter(c)),
                 }
 
             // numbers
@@ -178,7 +179,7 @@ impl State {
                     'e' => Ok(Some(DecE)),
                     c if c.is_digit(10) => Ok(Some(Dec)),
                     c if !c.is_alphanumeric() => Ok(None),
-                    _ => Err(Error::MalformedNumericLiteral),
+                    _ => Err(Error::MalformedNumericLiteral(c)),
                 }
 
             // 0x
@@ -186,7 +187,7 @@ impl State {
                 match c {
                     '_' => Ok(Some(ZeroX)),
                     c if c.is_digit(16) => Ok(Some(Hex)),
-                    _ => Err(Error::MalformedNumericLiteral),
+                    _ => Err(Error::MalformedNumericLiteral(c)),
                 }
 
             // 0x1
@@ -195,7 +196,7 @@ impl State {
                     '_' => Ok(Some(Hex)),
                     c if c.is_digit(16) => Ok(Some(Hex)),
                     c if !c.is_alphanumeric() => Ok(None),
-                    _ => Err(Error::MalformedNumericLiteral),
+                    _ => Err(Error::MalformedNumericLiteral(c)),
                 }
 
             // 0b
@@ -203,7 +204,7 @@ impl State {
                 match c {
                     '_' => Ok(Some(ZeroB)),
                     c if c.is_digit(2) => Ok(Some(Bin)),
-                    _ => Err(Error::MalformedNumericLiteral),
+                    _ => Err(Error::MalformedNumericLiteral(c)),
                 }
 
             // 0b1
@@ -212,7 +213,7 @@ impl State {
                     '_' => Ok(Some(Bin)),
                     c if c.is_digit(2) => Ok(Some(Bin)),
                     c if !c.is_alphanumeric() => Ok(None),
-                    _ => Err(Error::MalformedNumericLiteral)
+                    _ => Err(Error::MalformedNumericLiteral(c))
                 }
 
             // 1
@@ -223,7 +224,7 @@ impl State {
                     'e' => Ok(Some(DecE)),
                     c if c.is_digit(10) => Ok(Some(Dec)),
                     c if !c.is_alphanumeric() => Ok(None),
-                    _ => Err(Error::MalformedNumericLiteral)
+                    _ => Err(Error::MalformedNumericLiteral(c))
                 }
 
             // 1.
@@ -233,7 +234,7 @@ impl State {
                     'e' => Ok(Some(DecE)),
                     c if c.is_digit(10) => Ok(Some(DecFloat)),
                     c if !c.is_alphanumeric() => Ok(None),
-                    _ => Err(Error::MalformedNumericLiteral),
+                    _ => Err(Error::MalformedNumericLiteral(c)),
                 }
 
             // 1.3e
@@ -241,14 +242,14 @@ impl State {
                 match c {
                     '-' => Ok(Some(DecEMinus)),
                     c if c.is_digit(10) => Ok(Some(DecExp)),
-                    _ => Err(Error::MalformedNumericLiteral),
+                    _ => Err(Error::MalformedNumericLiteral(c)),
                 }
 
             // 1.3e-
             DecEMinus =>
                 match c {
                     c if c.is_digit(10) => Ok(Some(DecExp)),
-                    _ => Err(Error::MalformedNumericLiteral),
+                    _ => Err(Error::MalformedNumericLiteral(c)),
                 }
 
             // 1.3e-5
@@ -257,7 +258,7 @@ impl State {
                     '_' => Ok(Some(DecExp)),
                     c if c.is_digit(10) => Ok(Some(DecExp)),
                     c if !c.is_alphanumeric() => Ok(None),
-                    _ => Err(Error::MalformedNumericLiteral),
+                    _ => Err(Error::MalformedNumericLiteral(c)),
                 }
 
             // operators
@@ -339,7 +340,7 @@ impl State {
             CharStart =>
                 match c {
                     '\\' => Ok(Some(CharSlash)),
-                    '\'' => Err(Error::UnexpectedCharacter),
+                    '\'' => Err(Error::UnexpectedCharacter(c)),
                     _ => Ok(Some(CharX)),
                 }
 
@@ -347,7 +348,7 @@ impl State {
             CharX =>
                 match c {
                     '\'' => Ok(Some(Char)),
-                    _ => Err(Error::UnexpectedCharacter),
+                    _ => Err(Error::UnexpectedCharacter(c)),
                 }
 
             // '\
@@ -487,6 +488,7 @@ impl State {
             Equal =>
                 match c {
                     '=' => Ok(Some(EqualEqual)),
+                    '>' => Ok(Some(EqualMore)),
                     _ => Ok(None),
                 }
 
@@ -555,6 +557,7 @@ impl State {
                 }
 
             BarMore => Ok(None),
+            EqualMore => Ok(None),
 
             Dot =>
                 match c {
@@ -566,12 +569,12 @@ impl State {
             DotDot =>
                 match c {
                     '.' => Ok(Some(DotDotDot)),
-                    _ => Err(Error::UnexpectedCharacter),
+                    _ => Ok(None),
                 }
 
             DotDotDot =>
                 match c {
-                    '.' => Err(Error::UnexpectedCharacter),
+                    '.' => Err(Error::UnexpectedCharacter(c)),
                     _ => Ok(None),
                 }
 
@@ -580,25 +583,25 @@ impl State {
             Semi => Ok(None),
 
             // preprocessor
-            HashM => if c == 'a' { Ok(Some(HashMa)) } else { Err(Error::InvalidPreprocessorDirective) }
-            HashMa => if c == 'c' { Ok(Some(HashMac)) } else { Err(Error::InvalidPreprocessorDirective) },
-            HashMac => if c == 'r' { Ok(Some(HashMacr)) } else { Err(Error::InvalidPreprocessorDirective) },
-            HashMacr => if c == 'o' { Ok(Some(HashMacro)) } else { Err(Error::InvalidPreprocessorDirective) },
+            HashM => if c == 'a' { Ok(Some(HashMa)) } else { Err(Error::InvalidPreprocessorDirective(c)) }
+            HashMa => if c == 'c' { Ok(Some(HashMac)) } else { Err(Error::InvalidPreprocessorDirective(c)) },
+            HashMac => if c == 'r' { Ok(Some(HashMacr)) } else { Err(Error::InvalidPreprocessorDirective(c)) },
+            HashMacr => if c == 'o' { Ok(Some(HashMacro)) } else { Err(Error::InvalidPreprocessorDirective(c)) },
             HashMacro =>
                 match c {
-                    '_' => Err(Error::InvalidPreprocessorDirective),
-                    c if c.is_alphanumeric() => Err(Error::InvalidPreprocessorDirective),
+                    '_' => Err(Error::InvalidPreprocessorDirective(c)),
+                    c if c.is_alphanumeric() => Err(Error::InvalidPreprocessorDirective(c)),
                     _ => Ok(None),
                 }
-            HashP => if c == 'r' { Ok(Some(HashPr)) } else { Err(Error::InvalidPreprocessorDirective) },
-            HashPr => if c == 'a' { Ok(Some(HashPra)) } else { Err(Error::InvalidPreprocessorDirective) },
-            HashPra => if c == 'g' { Ok(Some(HashPrag)) } else { Err(Error::InvalidPreprocessorDirective) },
-            HashPrag => if c == 'm' { Ok(Some(HashPragm)) } else { Err(Error::InvalidPreprocessorDirective) },
-            HashPragm => if c == 'a' { Ok(Some(HashPragma)) } else { Err(Error::InvalidPreprocessorDirective) },
+            HashP => if c == 'r' { Ok(Some(HashPr)) } else { Err(Error::InvalidPreprocessorDirective(c)) },
+            HashPr => if c == 'a' { Ok(Some(HashPra)) } else { Err(Error::InvalidPreprocessorDirective(c)) },
+            HashPra => if c == 'g' { Ok(Some(HashPrag)) } else { Err(Error::InvalidPreprocessorDirective(c)) },
+            HashPrag => if c == 'm' { Ok(Some(HashPragm)) } else { Err(Error::InvalidPreprocessorDirective(c)) },
+            HashPragm => if c == 'a' { Ok(Some(HashPragma)) } else { Err(Error::InvalidPreprocessorDirective(c)) },
             HashPragma =>
                 match c {
-                    '_' => Err(Error::InvalidPreprocessorDirective),
-                    c if c.is_alphanumeric() => Err(Error::InvalidPreprocessorDirective),
+                    '_' => Err(Error::InvalidPreprocessorDirective(c)),
+                    c if c.is_alphanumeric() => Err(Error::InvalidPreprocessorDirective(c)),
                     _ => Ok(None),
                 },
 
@@ -1,3 +1,5 @@
+use std::fmt::{self, Display, Formatter};
+
 use super::state::State;
 
 /// All possible tokens from a gmlpp program
@@ -22,6 +24,7 @@ pub enum Token {
     Comment(String),
     DocComment(String),
     BlockComment(String),
+    FallthroughComment,
 
     // bitwise operators
     And,
@@ -75,6 +78,7 @@ pub enum Token {
     Colon,
     Pipe,
     Underscore,
+    Arrow,
 
     // delimiters
     LBrack,
@@ -85,6 +89,7 @@ pub enum Token {
     LBrace,
     Comma,
     Dot,
+    DotDot,
     DotDotDot,
     Semi,
     EOL,
@@ -211,6 +216,7 @@ impl Token {
             State::BarBar => BOr,
             State::BarEqual => OrEqual,
             State::BarMore => Pipe,
+            State::EqualMore => Arrow,
             State::Xor => Xor,
             State::XorEqual => XorEqual,
             State::Tilde => Inv,
@@ -222,6 +228,7 @@ impl Token {
             State::At => At,
             State::Underscore => Underscore,
             State::Dot => Dot,
+            State::DotDot => DotDot,
             State::DotDotDot => DotDotDot,
             State::Comma => Comma,
             State::Semi => Semi,
@@ -237,6 +244,23 @@ impl Token {
         }
     }
 
+    /// Whether this is a line comment marking a switch case as intentionally falling through, such
+    /// as `// fallthrough` or `// falls through`
+    pub fn is_fallthrough_comment(&self) -> bool {
+        match self {
+            Token::Comment(ref comment) => {
+                let words: String = comment
+                    .trim_start_matches('/')
+                    .chars()
+                    .filter(|c| c.is_alphabetic())
+                    .flat_map(|c| c.to_lowercase())
+                    .collect();
+                words.starts_with("fallthrough") || words.starts_with("fallsthrough")
+            }
+            _ => false,
+        }
+    }
+
     fn keyword(string: &str) -> Option<Token> {
         use self::Token::*;
         match string {
@@ -313,3 +337,142 @@ impl Token {
         }
     }
 }
+
+impl Display for Token {
+    /// Shows the token as it is written in the source, so it can be pointed out in an error
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use self::Token::*;
+        let text = match self {
+            Identifier(ref string) | BinLiteral(ref string) | HexLiteral(ref string)
+            | DecLiteral(ref string) | StrLiteral(ref string) | CharLiteral(ref string)
+            | Macro(ref string) | Pragma(ref string) | Comment(ref string) | DocComment(ref string)
+            | BlockComment(ref string) | TemplateLiteral(ref string) => return write!(f, "`{}`", string),
+            FallthroughComment => return write!(f, "fallthrough comment"),
+            MatchEqual => return write!(f, "match operator"),
+            EOL => return write!(f, "end of line"),
+            BOF => return write!(f, "start of input"),
+            EOF => return write!(f, "end of input"),
+            FalseLiteral => "false",
+            TrueLiteral => "true",
+            UndefinedLiteral => "undefined",
+            And => "&",
+            Or => "|",
+            Xor => "^",
+            Inv => "~",
+            LShift => "<<",
+            RShift => ">>",
+            AndEqual => "&=",
+            OrEqual => "|=",
+            XorEqual => "^=",
+            LShiftEqual => "<<=",
+            RShiftEqual => ">>=",
+            BAnd => "&&",
+            BOr => "||",
+            Bang => "!",
+            Plus => "+",
+            Minus => "-",
+            Star => "*",
+            Slash => "/",
+            Pct => "%",
+            Exp => "**",
+            PlusEqual => "+=",
+            MinusEqual => "-=",
+            StarEqual => "*=",
+            SlashEqual => "/=",
+            PctEqual => "%=",
+            ExpEqual => "**=",
+            PlusPlus => "++",
+            MinusMinus => "--",
+            Equal => "==",
+            Less => "<",
+            More => ">",
+            NotEqual => "!=",
+            LessEqual => "<=",
+            MoreEqual => ">=",
+            Assign => "=",
+            Hash => "#",
+            At => "@",
+            Question => "?",
+            Colon => ":",
+            Pipe => "|>",
+            Underscore => "_",
+            Arrow => "=>",
+            LBrack => "[",
+            RBrack => "]",
+            LParen => "(",
+            RParen => ")",
+            RBrace => "}",
+            LBrace => "{",
+            Comma => ",",
+            Dot => ".",
+            DotDot => "..",
+            DotDotDot => "...",
+            Semi => ";",
+            For => "for",
+            Do => "do",
+            While => "while",
+            Until => "until",
+            Repeat => "repeat",
+            Loop => "loop",
+            With => "with",
+            If => "if",
+            Else => "else",
+            Switch => "switch",
+            Case => "case",
+            Default => "default",
+            Break => "break",
+            Continue => "continue",
+            Return => "return",
+            Exit => "exit",
+            Var => "var",
+            Globalvar => "globalvar",
+            Enum => "enum",
+            Global => "global",
+            Div => "div",
+            Mod => "mod",
+            Argument => "argument",
+            Public => "public",
+            Protected => "protected",
+            Private => "private",
+            Let => "let",
+            Const => "const",
+            Function => "function",
+            Struct => "struct",
+            Type => "type",
+            Data => "data",
+            In => "in",
+            Is => "is",
+            Of => "of",
+            TypeOf => "typeof",
+            InstanceOf => "instanceof",
+            Match => "match",
+            Throw => "throw",
+            Catch => "catch",
+            Try => "try",
+            Unreachable => "unreachable",
+            Null => "null",
+            TBool => "Bool",
+            TNumber => "Number",
+            TString => "String",
+            TChar => "Char",
+            TArray => "Array",
+            TSymbol => "Symbol",
+            TVoid => "Void",
+            TNull => "Null",
+            TNever => "Never",
+            TMap => "Map",
+            TList => "List",
+            TGrid => "Grid",
+            TObject => "Object",
+            TRoom => "Room",
+            TSprite => "Sprite",
+            TScript => "Script",
+            TPath => "Path",
+            TTileset => "Tileset",
+            TSound => "Sound",
+            TFont => "Font",
+            TTimeline => "Timeline",
+        };
+        write!(f, "`{}`", text)
+    }
+}
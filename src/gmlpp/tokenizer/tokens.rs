@@ -1,19 +1,31 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::ops::{Index, Range, RangeTo};
-use super::Token;
+use super::{Token, Span};
+use error::Error;
 
 /// A sequence of tokens with a marker at the current position, like a Cursor but less awkward
 pub struct Tokens {
     tokens: Vec<Token>,
+    spans: Vec<Span>,
     pos: Cell<usize>,
+    /// The furthest position that has been reached, which is where the parser gave up if it fails
+    furthest: Cell<usize>,
+    /// Syntax errors which the parser has recovered from, with the position each was found at
+    errors: RefCell<Vec<(usize, Error)>>,
+    /// Whether the source had comments which were removed, as the parser has nowhere to put them
+    comments: bool,
 }
 
 impl Tokens {
     /// Creates a new instance
-    pub fn new(tokens: Vec<Token>) -> Self {
+    pub fn new(tokens: Vec<Token>, spans: Vec<Span>, comments: bool) -> Self {
         Tokens {
             tokens,
+            spans,
             pos: Cell::new(0),
+            furthest: Cell::new(0),
+            errors: RefCell::new(vec![]),
+            comments,
         }
     }
 
@@ -31,7 +43,7 @@ impl Tokens {
 
     /// Increments the internal counter by some amount
     pub fn skip(&self, n: usize) {
-        self.pos.set(self.pos.get() + n);
+        self.seek(self.pos.get() + n);
     }
 
     /// Decrements the internal counter by some amount
@@ -39,12 +51,63 @@ impl Tokens {
         self.pos.set(self.pos.get() - n);
     }
 
+    /// Where the current token is in the source file
+    pub fn span(&self) -> Span {
+        self.spans[self.pos.get()]
+    }
+
+    /// The span of all the tokens from a position up to the current one, not including it
+    pub fn span_from(&self, pos: usize) -> Span {
+        let end = self.pos.get().max(pos + 1) - 1;
+        self.spans[pos].to(self.spans[end])
+    }
+
+    /// Records where the parser gave up, and what it found there, on an error it could not parse past
+    pub fn locate(&self, error: Error) -> Error {
+        let furthest = self.furthest.get();
+        error.found(self.tokens[furthest].clone()).at(self.spans[furthest])
+    }
+
+    /// Starts measuring how far the parser gets from the current position
+    pub fn reset_furthest(&self) {
+        self.furthest.set(self.pos.get());
+    }
+
+    /// Records a syntax error which the parser has recovered from
+    pub fn report(&self, error: Error) {
+        self.errors.borrow_mut().push((self.pos.get(), error));
+    }
+
+    /// Takes all the syntax errors which have been recovered from
+    pub fn take_errors(&self) -> Vec<Error> {
+        self.errors.replace(vec![]).into_iter().map(|(_, error)| error).collect()
+    }
+
+    /// Whether any comments were removed from the source, so would be lost if it was printed again
+    pub fn has_comments(&self) -> bool {
+        self.comments
+    }
+
     pub fn pos(&self) -> usize {
         self.pos.get()
     }
 
+    /// Moves to a position. Moving back means the parser is backtracking, and will parse those tokens
+    /// again, so the errors it recovered from in them are forgotten rather than reported twice
     pub fn seek(&self, pos: usize) {
+        if pos < self.pos.get() {
+            self.errors.borrow_mut().retain(|&(found, _)| found < pos);
+        }
+        self.retreat(pos);
+    }
+
+    /// Moves to a position without forgetting any errors, to skip over tokens which could not be parsed
+    /// after reporting the errors in them
+    pub fn retreat(&self, pos: usize) {
         self.pos.set(pos);
+        if pos > self.furthest.get() {
+            self.furthest.set(pos);
+        }
     }
 }
 
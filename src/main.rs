@@ -4,25 +4,113 @@
 extern crate serde;
 extern crate serde_json;
 extern crate notify;
+extern crate xmltree;
 
 mod project;
 mod gml;
 mod gmlpp;
 mod compiler;
+mod symbols;
+mod checker;
 mod error;
 
 use std::env;
+use std::process;
+
+use self::compiler::{Compiler, MessageFormat};
+use self::error::Error;
+use self::project::Project;
+
+const USAGE: &str = "\
+Usage: gmlpp <command> [project file] [--message-format=human|json]
+
+Commands:
+    build    Compile every .gmlpp file in the project once
+    watch    Compile every .gmlpp file, then again whenever one changes
+    check    Report problems in the .gmlpp files without writing any .gml files
+    fmt      Rewrite the .gmlpp files in the standard style
+    clean    Remove the .gml files which are generated from .gmlpp files
+    help     Show this message
+
+The project file is either a .yyp file, or a .project.gmx file from GameMaker Studio 1.4.
+When it is not supplied, the one in the current directory is used";
+
+/// What the compiler has been asked to do
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Command {
+    Build,
+    Watch,
+    Check,
+    Fmt,
+    Clean,
+    Help,
+}
+
+impl Command {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "build" => Some(Command::Build),
+            "watch" => Some(Command::Watch),
+            "check" => Some(Command::Check),
+            "fmt" => Some(Command::Fmt),
+            "clean" => Some(Command::Clean),
+            "help" | "--help" | "-h" => Some(Command::Help),
+            _ => None,
+        }
+    }
+}
 
 fn main() {
-    run().unwrap();
+    match run() {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            process::exit(2);
+        }
+    }
 }
 
-fn run() -> Result<(), self::error::Error> {
-    let args = env::args();
-    // TODO: might be nice to predict project file names if they are not supplied
-    let project_file = args.into_iter().nth(1).ok_or(self::error::Error::ArgumentError)?;
-    let project = self::project::Project::new(project_file)?;
-    let compiler = self::compiler::Compiler::new(project);
-    compiler.compile_all()?;
-    compiler.watch()
+/// Runs the command from the arguments, returning whether it succeeded
+fn run() -> Result<bool, Error> {
+    let mut command = None;
+    let mut project_file = None;
+    let mut message_format = MessageFormat::default();
+    for arg in env::args().skip(1) {
+        if arg.starts_with("--message-format=") {
+            message_format = arg["--message-format=".len()..].parse()?;
+        } else if let (None, Some(parsed)) = (command, Command::parse(&arg)) {
+            command = Some(parsed);
+        } else if project_file.is_none() && (arg.ends_with(".yyp") || arg.ends_with(".project.gmx")) {
+            project_file = Some(arg);
+        } else {
+            return Err(Error::UnknownArgument(arg));
+        }
+    }
+    // a project file on its own is compiled and watched, as that is all there used to be
+    let command = match (command, &project_file) {
+        (Some(command), _) => command,
+        (None, &Some(..)) => Command::Watch,
+        (None, &None) => Command::Help,
+    };
+    if command == Command::Help {
+        println!("{}", USAGE);
+        return Ok(true);
+    }
+    let project_file = match project_file {
+        Some(project_file) => project_file,
+        None => Project::discover(env::current_dir()?)?,
+    };
+    let compiler = Compiler::new(Project::new(project_file)?, message_format);
+    match command {
+        Command::Build => compiler.compile_all(),
+        Command::Watch => {
+            compiler.compile_all()?;
+            compiler.watch().map(|_| true)
+        }
+        Command::Check => compiler.check_all(),
+        Command::Fmt => compiler.format_all(),
+        Command::Clean => compiler.clean().map(|_| true),
+        Command::Help => unreachable!(),
+    }
 }
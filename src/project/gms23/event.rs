@@ -0,0 +1,23 @@
+use super::ResourceReference;
+use super::super::resource::EventType;
+use super::super::ID;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all="camelCase")]
+pub struct Event {
+    event_num: i32,
+    event_type: i32,
+    collision_object_id: Option<ResourceReference>,
+}
+
+impl Event {
+    /// Which event this is, if it is a kind that GameMaker Studio 2 has. Collision event files are
+    /// named after the object collided with, rather than its id
+    pub fn event_type(&self) -> Option<EventType> {
+        let collision_object = self.collision_object_id
+            .as_ref()
+            .map(ResourceReference::id)
+            .unwrap_or(ID(String::new()));
+        EventType::new(self.event_type, self.event_num, &collision_object)
+    }
+}
@@ -0,0 +1,115 @@
+//! The layout of projects made with GameMaker Studio 2.3 and later, where resources refer to each
+//! other by name and path rather than by id
+
+use serde_json::Value;
+
+use super::resource::{ResourceTag, ResourceType};
+use super::ID;
+
+mod object;
+mod event;
+mod script;
+
+pub use self::object::Object;
+pub use self::event::Event;
+pub use self::script::Script;
+
+/// Whether a `.yyp` or `.yy` file is in the 2.3 layout, which is told apart by naming its type in
+/// `resourceType` instead of `modelName`
+pub fn is_gms23(value: &Value) -> bool {
+    value.get("resourceType").is_some()
+}
+
+/// Data representation of a `.yyp` file. Only the fields which are used are read, so projects which
+/// leave out any of the others still load
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct YYP {
+    resources: Vec<ResourceEntry>,
+}
+
+impl YYP {
+    /// Tags the resources of this project the same way as an older one does. Only the kinds of
+    /// resource that are used are kept
+    pub fn tags(&self) -> Vec<ResourceTag> {
+        self.resources
+            .iter()
+            .filter_map(|entry| {
+                let resource_type = entry.id.resource_type()?;
+                Some(ResourceTag::new(entry.id.id(), entry.id.path.clone(), resource_type))
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct ResourceEntry {
+    id: ResourceReference,
+}
+
+/// Refers to another resource, which is named after its `.yy` file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ResourceReference {
+    name: String,
+    path: String,
+}
+
+impl ResourceReference {
+    /// Resources are identified by their names
+    pub fn id(&self) -> ID {
+        ID(self.name.clone())
+    }
+
+    /// The kind of resource this refers to, going by the folder resources of that kind are kept in
+    fn resource_type(&self) -> Option<ResourceType> {
+        match self.path.split(|c| c == '/' || c == '\\').next()? {
+            "objects" => Some(ResourceType::Object),
+            "scripts" => Some(ResourceType::Script),
+            "sprites" => Some(ResourceType::Sprite),
+            "rooms" => Some(ResourceType::Room),
+            "tilesets" => Some(ResourceType::TileSet),
+            "fonts" => Some(ResourceType::Font),
+            "sounds" => Some(ResourceType::Sound),
+            "shaders" => Some(ResourceType::Shader),
+            "timelines" => Some(ResourceType::Timeline),
+            "notes" => Some(ResourceType::Note),
+            "extensions" => Some(ResourceType::Extension),
+            _ => None,
+        }
+    }
+}
+
+/// The contents of a `.yy` file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag="resourceType")]
+pub enum YY {
+    #[serde(rename="GMObject")]
+    Object(Object),
+    #[serde(rename="GMScript")]
+    Script(Script),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use project::Project;
+
+    #[test]
+    fn loads_project_without_unused_fields() {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/gms23");
+        let project = Project::new(directory.join("Game.yyp").to_string_lossy().into_owned()).unwrap();
+        let sources: Vec<_> = project
+            .sources()
+            .iter()
+            .map(|source| source.gmlpp().strip_prefix(&directory).unwrap().to_owned())
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                Path::new("objects/obj_player/Create_0.gmlpp"),
+                Path::new("objects/obj_player/Collision_obj_wall.gmlpp"),
+                Path::new("scripts/scr_move/scr_move.gmlpp"),
+            ],
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use super::Event;
+use super::super::source::Source;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all="camelCase")]
+pub struct Object {
+    event_list: Vec<Event>,
+}
+
+impl Object {
+    /// Returns the source files for this Object. One for each event.
+    pub fn sources(&self) -> Vec<Source> {
+        self.event_list
+            .iter()
+            // events of kinds that are not known about have no file that can be found
+            .filter_map(|event| event.event_type())
+            .map(|event_type| Source::from(event_type.file_name()))
+            .collect()
+    }
+}
@@ -0,0 +1,13 @@
+use super::super::source::Source;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Script {
+    name: String,
+}
+
+impl Script {
+    /// Returns the source files for this script. Should be just the one.
+    pub fn sources(&self) -> Vec<Source> {
+        vec![Source::from(format!("{}.gml", self.name))]
+    }
+}
@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use xmltree::{Element, XMLNode};
+
+use error::Error;
+
+/// The kind of action which runs a piece of code
+const CODE_ACTION_KIND: &str = "7";
+
+/// The code action of an event in an object's `.object.gmx` file, which compiled code is put in
+#[derive(Clone, Debug)]
+pub struct CodeAction {
+    object_file: PathBuf,
+    eventtype: i32,
+    enumb: i32,
+    ename: Option<String>,
+}
+
+impl CodeAction {
+    pub fn new(object_file: PathBuf, eventtype: i32, enumb: i32, ename: Option<String>) -> Self {
+        CodeAction { object_file, eventtype, enumb, ename }
+    }
+
+    /// Adjusts the path of the object file to the provided base path
+    pub fn resolved_to(self, mut path: PathBuf) -> Self {
+        path.push(self.object_file);
+        CodeAction { object_file: path, ..self }
+    }
+
+    /// Replaces the code of the action, writing the object file back out
+    pub fn write(&self, code: &str) -> Result<(), Error> {
+        let mut root = super::read(&self.object_file)?;
+        {
+            let string = self.find(&mut root).ok_or_else(|| Error::MissingCodeAction.in_file(&self.object_file))?;
+            string.children = vec![XMLNode::Text(code.to_owned())];
+        }
+        super::write(&self.object_file, &root)
+    }
+
+    /// Finds the element holding the code of the first code action in the event
+    fn find<'a>(&self, root: &'a mut Element) -> Option<&'a mut Element> {
+        root.get_mut_child("events")?
+            .children
+            .iter_mut()
+            .filter_map(XMLNode::as_mut_element)
+            .find(|event| self.is(event))?
+            .children
+            .iter_mut()
+            .filter_map(XMLNode::as_mut_element)
+            .find(|action| {
+                action.name == "action"
+                    && action.get_child("kind").and_then(Element::get_text).map_or(false, |kind| kind == CODE_ACTION_KIND)
+            })?
+            .get_mut_child("arguments")?
+            .get_mut_child("argument")?
+            .get_mut_child("string")
+    }
+
+    /// Whether an `<event>` element is the one this action is in
+    fn is(&self, event: &Element) -> bool {
+        let attribute = |name| event.attributes.get(name).map(String::as_str);
+        let eventtype = self.eventtype.to_string();
+        let enumb = self.enumb.to_string();
+        attribute("eventtype") == Some(&eventtype)
+            && match self.ename {
+                Some(ref ename) => attribute("ename") == Some(ename),
+                None => attribute("enumb").unwrap_or("0") == enumb,
+            }
+    }
+}
@@ -0,0 +1,114 @@
+//! The layout of projects made with GameMaker Studio 1.4, which are described in XML. The code of
+//! scripts is kept in `.gml` files, but the code of events is kept in the object's XML
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use xmltree::{Element, EmitterConfig, XMLNode};
+
+use error::Error;
+use super::resource::{ResourceTag, ResourceType};
+use super::ID;
+
+mod object;
+mod script;
+mod code_action;
+
+pub use self::object::Object;
+pub use self::script::Script;
+pub use self::code_action::CodeAction;
+
+/// Whether a project file is a GameMaker Studio 1.4 `.project.gmx` file
+pub fn is_gmx_project<P: AsRef<Path>>(path: P) -> bool {
+    path.as_ref().to_string_lossy().ends_with(".project.gmx")
+}
+
+/// Whether a resource is one from a GameMaker Studio 1.4 project
+pub fn is_gmx_resource<P: AsRef<Path>>(path: P) -> bool {
+    let path = path.as_ref().to_string_lossy();
+    path.ends_with(".object.gmx") || path.ends_with(".gml")
+}
+
+/// Tags the objects and scripts in a `.project.gmx` file the same way as a newer project does.
+/// They are listed in nested groups, and are identified by their names
+pub fn tags<P: AsRef<Path>>(project_file: P) -> Result<Vec<ResourceTag>, Error> {
+    let project = read(project_file)?;
+    let mut tags = vec![];
+    collect_tags(&project, &mut tags);
+    Ok(tags)
+}
+
+fn collect_tags(group: &Element, tags: &mut Vec<ResourceTag>) {
+    for element in group.children.iter().filter_map(XMLNode::as_element) {
+        let path = element.get_text().unwrap_or_default().into_owned();
+        let name = path.rsplit(|c| c == '/' || c == '\\').next().unwrap_or("").split('.').next().unwrap_or("");
+        match element.name.as_str() {
+            "object" => tags.push(ResourceTag::new(ID(name.to_owned()), format!("{}.object.gmx", path), ResourceType::Object)),
+            "script" => tags.push(ResourceTag::new(ID(name.to_owned()), path.clone(), ResourceType::Script)),
+            _ => collect_tags(element, tags),
+        }
+    }
+}
+
+/// The contents of a resource's file
+#[derive(Clone, Debug)]
+pub enum YY {
+    Object(Object),
+    Script(Script),
+}
+
+impl YY {
+    /// Loads a resource. Scripts are just code, so only objects need to be read
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        if path.to_string_lossy().ends_with(".gml") {
+            Ok(YY::Script(Script::new(path)))
+        } else {
+            Ok(YY::Object(Object::new(path, &read(path)?)))
+        }
+    }
+}
+
+/// Reads an XML file
+fn read<P: AsRef<Path>>(path: P) -> Result<Element, Error> {
+    let file = File::open(path.as_ref()).map_err(|_| Error::missing_resource(path.as_ref()))?;
+    Element::parse(file).map_err(|error| Error::from(error).in_file(path.as_ref()))
+}
+
+/// Writes an XML file the way GameMaker does, with the comment it puts at the top
+fn write<P: AsRef<Path>>(path: P, root: &Element) -> Result<(), Error> {
+    let mut file = File::create(path.as_ref())?;
+    write!(file, "<!--This Document is generated by GameMaker, if you edit it by hand then you do so at your own risk!-->\r\n")?;
+    let config = EmitterConfig::new()
+        .write_document_declaration(false)
+        .perform_indent(true)
+        .indent_string("  ")
+        .line_separator("\r\n");
+    root.write_with_config(&mut file, config).map_err(|error| Error::from(error).in_file(path.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use project::Project;
+
+    #[test]
+    fn loads_project_with_events_in_the_object_files() {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/gmx");
+        let project = Project::new(directory.join("Game.project.gmx").to_string_lossy().into_owned()).unwrap();
+        let sources: Vec<_> = project
+            .sources()
+            .iter()
+            .map(|source| source.gmlpp().strip_prefix(&directory).unwrap().to_owned())
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                Path::new("objects/obj_player/Create_0.gmlpp"),
+                Path::new("objects/obj_player/Collision_obj_wall.gmlpp"),
+                Path::new("scripts/scr_move.gmlpp"),
+            ],
+        );
+    }
+}
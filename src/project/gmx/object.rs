@@ -0,0 +1,61 @@
+use std::path::{Path, PathBuf};
+
+use xmltree::{Element, XMLNode};
+
+use super::CodeAction;
+use super::super::resource::EventType;
+use super::super::source::Source;
+use super::super::ID;
+
+/// An object, which is described by a `.object.gmx` file along with the code of its events
+#[derive(Clone, Debug)]
+pub struct Object {
+    name: String,
+    file_name: PathBuf,
+    events: Vec<Event>,
+}
+
+/// An event of an object, told apart from the others by the attributes of its `<event>` element
+#[derive(Clone, Debug)]
+struct Event {
+    eventtype: i32,
+    enumb: i32,
+    /// Collision events name the object collided with instead of having a number
+    ename: Option<String>,
+}
+
+impl Object {
+    pub fn new(path: &Path, root: &Element) -> Self {
+        let file_name = PathBuf::from(path.file_name().unwrap_or_default());
+        let name = file_name.to_string_lossy().split('.').next().unwrap_or("").to_owned();
+        let events = root
+            .get_child("events")
+            .map(|events| events.children.iter().filter_map(XMLNode::as_element).filter_map(Event::new).collect())
+            .unwrap_or_default();
+        Object { name, file_name, events }
+    }
+
+    /// Returns the source files for this Object. One for each event, kept in a folder named after
+    /// the object as in newer projects. Their code goes into the object's file when compiled
+    pub fn sources(&self) -> Vec<Source> {
+        self.events
+            .iter()
+            .filter_map(|event| {
+                let collision_object = ID(event.ename.clone().unwrap_or_default());
+                let event_type = EventType::new(event.eventtype, event.enumb, &collision_object)?;
+                let path = Path::new(&self.name).join(event_type.file_name());
+                let action = CodeAction::new(self.file_name.clone(), event.eventtype, event.enumb, event.ename.clone());
+                Some(Source::from(path).in_action(action))
+            })
+            .collect()
+    }
+}
+
+impl Event {
+    fn new(element: &Element) -> Option<Self> {
+        let eventtype = element.attributes.get("eventtype")?.parse().ok()?;
+        let enumb = element.attributes.get("enumb").and_then(|enumb| enumb.parse().ok()).unwrap_or(0);
+        let ename = element.attributes.get("ename").cloned();
+        Some(Event { eventtype, enumb, ename })
+    }
+}
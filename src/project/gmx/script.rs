@@ -0,0 +1,21 @@
+use std::path::Path;
+
+use super::super::source::Source;
+
+/// A script, which is a `.gml` file in the scripts folder
+#[derive(Clone, Debug)]
+pub struct Script {
+    name: String,
+}
+
+impl Script {
+    pub fn new(path: &Path) -> Self {
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        Script { name }
+    }
+
+    /// Returns the source files for this script. Should be just the one.
+    pub fn sources(&self) -> Vec<Source> {
+        vec![Source::from(format!("{}.gml", self.name))]
+    }
+}
@@ -0,0 +1,41 @@
+use std::io::Read;
+
+use serde::de::DeserializeOwned;
+use serde_json;
+
+use error::Error;
+
+/// Reads JSON the way GameMaker writes it. Since version 2.3 it leaves a comma after the last item
+/// of every object and array, which is not allowed in JSON, so those are removed first
+pub fn from_reader<R: Read, T: DeserializeOwned>(mut reader: R) -> Result<T, Error> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    Ok(serde_json::from_str(&without_trailing_commas(&text))?)
+}
+
+fn without_trailing_commas(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            // the comma is trailing if only whitespace comes before the closing bracket
+            let rest = chars.clone().skip_while(|c| c.is_whitespace()).next();
+            if rest == Some('}') || rest == Some(']') {
+                continue;
+            }
+        }
+        output.push(c);
+    }
+    output
+}
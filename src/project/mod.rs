@@ -5,6 +5,9 @@ mod resource;
 mod project;
 mod parent_project;
 mod source;
+mod json;
+mod gms23;
+mod gmx;
 
 pub use self::project::Project;
 pub use self::source::Source;
@@ -1,16 +1,16 @@
-use std::fs::File;
+use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
 use std::cell::RefCell;
 
-use serde_json;
+use serde_json::{self, Value};
 
 use error::Error;
 use super::parent_project::ParentProject;
 use super::resource::{Resource, ResourceTag, ResourceType};
 use super::model::Model;
 use super::source::Source;
-use super::ID;
+use super::{gms23, gmx, json, ID};
 
 /// Data representation of a `.yyp` file
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -34,28 +34,69 @@ struct YYP {
 #[derive(Clone, Debug)]
 pub struct Project {
     project_file: String,
-    project: YYP,
+    tags: Vec<ResourceTag>,
     resources: RefCell<HashMap<ID, Resource>>,
 }
 
 impl Project {
-    /// Loads a GameMaker Studio 2 project from a `.yyp` file
+    /// Loads a GameMaker Studio 2 project from a `.yyp` file, in the layout of any version, or a
+    /// GameMaker Studio 1.4 project from a `.project.gmx` file
     pub fn new(project_file: String) -> Result<Self, Error> {
-        let f = File::open(project_file.clone()).map_err(|_| Error::NoProject)?;
-        serde_json::from_reader(f)
-            .map(move |project| Self { project_file, project, resources: RefCell::new(HashMap::new()) })
-            .map_err(From::from)
+        let f = File::open(project_file.clone()).map_err(|_| Error::NoProject(project_file.clone().into()))?;
+        let tags = if gmx::is_gmx_project(&project_file) {
+            gmx::tags(&project_file)?
+        } else {
+            Self::tags(f).map_err(|error| error.in_file(&project_file))?
+        };
+        Ok(Self { project_file, tags, resources: RefCell::new(HashMap::new()) })
+    }
+
+    /// Reads the tags of the resources in a project file
+    fn tags(file: File) -> Result<Vec<ResourceTag>, Error> {
+        let value: Value = json::from_reader(file)?;
+        if gms23::is_gms23(&value) {
+            let project: gms23::YYP = serde_json::from_value(value)?;
+            Ok(project.tags())
+        } else {
+            let project: YYP = serde_json::from_value(value)?;
+            Ok(project.resources)
+        }
+    }
+
+    /// Finds the `.yyp` or `.project.gmx` file in a directory, so it does not need to be named when there is only one
+    pub fn discover<P: AsRef<Path>>(directory: P) -> Result<String, Error> {
+        let mut project_files: Vec<PathBuf> = fs::read_dir(directory)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().map_or(false, |extension| extension == "yyp") || gmx::is_gmx_project(path))
+            .collect();
+        match project_files.len() {
+            0 => Err(Error::ProjectNotFound),
+            1 => Ok(project_files.remove(0).to_string_lossy().into_owned()),
+            _ => Err(Error::AmbiguousProject(project_files)),
+        }
     }
 
     /// The directory this project file is in
     pub fn directory(&self) -> PathBuf {
-        Path::new(&self.project_file).parent().unwrap().to_owned()
+        match Path::new(&self.project_file).parent() {
+            // a project file named without a directory is in the current one
+            Some(directory) if directory != Path::new("") => directory.to_owned(),
+            _ => PathBuf::from("."),
+        }
+    }
+
+    /// The name of the object with an id, if there is one in this project
+    pub fn object_name(&self, id: &ID) -> Option<String> {
+        self.tags
+            .iter()
+            .find(|tag| tag.resource_type() == ResourceType::Object && tag.id() == id)
+            .and_then(|tag| tag.name())
     }
 
     /// Finds all the source files for all resources
     pub fn sources(&self) -> Vec<Source> {
-        self.project
-            .resources
+        self.tags
             .iter()
             .filter(|tag| 
                 tag.resource_type() == ResourceType::Object ||
@@ -1,3 +1,5 @@
+use std::fmt::{self, Display, Formatter};
+
 use super::super::ID;
 use super::super::model::Model;
 
@@ -17,40 +19,333 @@ pub struct Event {
 }
 
 impl Event {
-    pub fn event_type(&self) -> EventType {
-        use self::EventType::*;
-        match self.eventtype {
-            0 => Create(self.enumb),
-            3 => Step(self.enumb),
-            4 => Collision(self.id.clone()),
-            7 => Other(self.enumb),
-            8 => Draw(self.enumb),
-            12 => CleanUp(self.enumb),
-            _ => unimplemented!(),
-        }
+    /// Which event this is, if it is a kind that GameMaker Studio 2 has
+    pub fn event_type(&self) -> Option<EventType> {
+        EventType::new(self.eventtype, self.enumb, &self.collision_object_id)
     }
 }
 
-#[derive(Clone, Debug)]
-enum EventType {
+/// The kinds of events an object can have, with the number which picks out the particular event of
+/// that kind, such as which alarm or key it is
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EventType {
     Create(i32),
+    Destroy(i32),
+    Alarm(i32),
     Step(i32),
     Collision(ID),
+    Keyboard(i32),
+    Mouse(i32),
     Other(i32),
     Draw(i32),
+    KeyPress(i32),
+    KeyRelease(i32),
+    Trigger(i32),
     CleanUp(i32),
+    Gesture(i32),
 }
 
 impl EventType {
+    /// Collision events are told apart by the id of the object they are collisions with, rather than
+    /// by number
+    pub fn new(eventtype: i32, enumb: i32, collision_object_id: &ID) -> Option<Self> {
+        use self::EventType::*;
+        match eventtype {
+            0 => Some(Create(enumb)),
+            1 => Some(Destroy(enumb)),
+            2 => Some(Alarm(enumb)),
+            3 => Some(Step(enumb)),
+            4 => Some(Collision(collision_object_id.clone())),
+            5 => Some(Keyboard(enumb)),
+            6 => Some(Mouse(enumb)),
+            7 => Some(Other(enumb)),
+            8 => Some(Draw(enumb)),
+            9 => Some(KeyPress(enumb)),
+            10 => Some(KeyRelease(enumb)),
+            11 => Some(Trigger(enumb)),
+            12 => Some(CleanUp(enumb)),
+            13 => Some(Gesture(enumb)),
+            _ => None,
+        }
+    }
+
+    /// Works out which event a source file is for from its name, such as `Alarm_3.gml`
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        use self::EventType::*;
+        let stem = file_name.split('.').next()?;
+        // the object collided with is named by its id, or by its name which may have underscores in it
+        if stem.starts_with("Collision_") {
+            return Some(Collision(ID(stem["Collision_".len()..].to_owned())));
+        }
+        let underscore = stem.rfind('_')?;
+        let (kind, number) = (&stem[..underscore], &stem[underscore + 1..]);
+        let number = number.parse().ok()?;
+        match kind {
+            "Create" => Some(Create(number)),
+            "Destroy" => Some(Destroy(number)),
+            "Alarm" => Some(Alarm(number)),
+            "Step" => Some(Step(number)),
+            "Keyboard" => Some(Keyboard(number)),
+            "Mouse" => Some(Mouse(number)),
+            "Other" => Some(Other(number)),
+            "Draw" => Some(Draw(number)),
+            "KeyPress" => Some(KeyPress(number)),
+            "KeyRelease" => Some(KeyRelease(number)),
+            "Trigger" => Some(Trigger(number)),
+            "CleanUp" => Some(CleanUp(number)),
+            "Gesture" => Some(Gesture(number)),
+            _ => None,
+        }
+    }
+
     pub fn file_name(&self) -> String {
         use self::EventType::*;
         match self {
             &Create(num) => format!("Create_{}.gml", num),
+            &Destroy(num) => format!("Destroy_{}.gml", num),
+            &Alarm(num) => format!("Alarm_{}.gml", num),
             &Step(num) => format!("Step_{}.gml", num),
             &Collision(ref id) => format!("Collision_{}.gml", id),
+            &Keyboard(num) => format!("Keyboard_{}.gml", num),
+            &Mouse(num) => format!("Mouse_{}.gml", num),
             &Other(num) => format!("Other_{}.gml", num),
             &Draw(num) => format!("Draw_{}.gml", num),
+            &KeyPress(num) => format!("KeyPress_{}.gml", num),
+            &KeyRelease(num) => format!("KeyRelease_{}.gml", num),
+            &Trigger(num) => format!("Trigger_{}.gml", num),
             &CleanUp(num) => format!("CleanUp_{}.gml", num),
+            &Gesture(num) => format!("Gesture_{}.gml", num),
+        }
+    }
+}
+
+impl Display for EventType {
+    /// Names the event the way the GameMaker Studio 2 IDE does
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use self::EventType::*;
+        match self {
+            &Create(..) => write!(f, "Create"),
+            &Destroy(..) => write!(f, "Destroy"),
+            &Alarm(num) => write!(f, "Alarm {}", num),
+            &Step(0) => write!(f, "Step"),
+            &Step(1) => write!(f, "Begin Step"),
+            &Step(2) => write!(f, "End Step"),
+            &Collision(..) => write!(f, "Collision"),
+            &Keyboard(key) => write!(f, "Keyboard - {}", KeyName(key)),
+            &Mouse(num) => match mouse_name(num) {
+                Some(name) => write!(f, "Mouse - {}", name),
+                None => write!(f, "Mouse {}", num),
+            },
+            &Other(num @ 10..=25) => write!(f, "User Event {}", num - 10),
+            &Other(num @ 40..=47) => write!(f, "Outside View {}", num - 40),
+            &Other(num @ 50..=57) => write!(f, "Intersect View {} Boundary", num - 50),
+            &Other(num) => match other_name(num) {
+                Some(name) => write!(f, "{}", name),
+                None => write!(f, "Other {}", num),
+            },
+            &Draw(0) => write!(f, "Draw"),
+            &Draw(64) => write!(f, "Draw GUI"),
+            &Draw(65) => write!(f, "Window Resize"),
+            &Draw(72) => write!(f, "Draw Begin"),
+            &Draw(73) => write!(f, "Draw End"),
+            &Draw(74) => write!(f, "Draw GUI Begin"),
+            &Draw(75) => write!(f, "Draw GUI End"),
+            &Draw(76) => write!(f, "Pre-Draw"),
+            &Draw(77) => write!(f, "Post-Draw"),
+            &KeyPress(key) => write!(f, "Key Press - {}", KeyName(key)),
+            &KeyRelease(key) => write!(f, "Key Release - {}", KeyName(key)),
+            &Trigger(num) => write!(f, "Trigger {}", num),
+            &CleanUp(..) => write!(f, "Clean Up"),
+            &Gesture(num) => match gesture_name(num) {
+                Some(name) => write!(f, "Gesture - {}", name),
+                None => write!(f, "Gesture {}", num),
+            },
+            &Step(num) => write!(f, "Step {}", num),
+            &Draw(num) => write!(f, "Draw {}", num),
+        }
+    }
+}
+
+fn mouse_name(num: i32) -> Option<&'static str> {
+    match num {
+        0 => Some("Left Down"),
+        1 => Some("Right Down"),
+        2 => Some("Middle Down"),
+        3 => Some("No Button"),
+        4 => Some("Left Pressed"),
+        5 => Some("Right Pressed"),
+        6 => Some("Middle Pressed"),
+        7 => Some("Left Released"),
+        8 => Some("Right Released"),
+        9 => Some("Middle Released"),
+        10 => Some("Mouse Enter"),
+        11 => Some("Mouse Leave"),
+        50 => Some("Global Left Down"),
+        51 => Some("Global Right Down"),
+        52 => Some("Global Middle Down"),
+        53 => Some("Global Left Pressed"),
+        54 => Some("Global Right Pressed"),
+        55 => Some("Global Middle Pressed"),
+        56 => Some("Global Left Released"),
+        57 => Some("Global Right Released"),
+        58 => Some("Global Middle Released"),
+        60 => Some("Mouse Wheel Up"),
+        61 => Some("Mouse Wheel Down"),
+        _ => None,
+    }
+}
+
+fn other_name(num: i32) -> Option<&'static str> {
+    match num {
+        0 => Some("Outside Room"),
+        1 => Some("Intersect Boundary"),
+        2 => Some("Game Start"),
+        3 => Some("Game End"),
+        4 => Some("Room Start"),
+        5 => Some("Room End"),
+        7 => Some("Animation End"),
+        8 => Some("Path Ended"),
+        30 => Some("Close Button"),
+        58 => Some("Animation Update"),
+        59 => Some("Animation Event"),
+        60 => Some("Async - Image Loaded"),
+        62 => Some("Async - HTTP"),
+        63 => Some("Async - Dialog"),
+        66 => Some("Async - In-App Purchase"),
+        67 => Some("Async - Cloud"),
+        68 => Some("Async - Networking"),
+        69 => Some("Async - Steam"),
+        70 => Some("Async - Social"),
+        71 => Some("Async - Push Notification"),
+        72 => Some("Async - Save/Load"),
+        73 => Some("Async - Audio Recording"),
+        74 => Some("Async - Audio Playback"),
+        75 => Some("Async - System"),
+        76 => Some("Broadcast Message"),
+        _ => None,
+    }
+}
+
+fn gesture_name(num: i32) -> Option<&'static str> {
+    match num {
+        0 => Some("Tap"),
+        1 => Some("Double Tap"),
+        2 => Some("Drag Start"),
+        3 => Some("Dragging"),
+        4 => Some("Drag End"),
+        5 => Some("Flick"),
+        6 => Some("Pinch Start"),
+        7 => Some("Pinch In"),
+        8 => Some("Pinch Out"),
+        9 => Some("Pinch End"),
+        10 => Some("Rotate Start"),
+        11 => Some("Rotating"),
+        12 => Some("Rotate End"),
+        64 => Some("Global Tap"),
+        65 => Some("Global Double Tap"),
+        66 => Some("Global Drag Start"),
+        67 => Some("Global Dragging"),
+        68 => Some("Global Drag End"),
+        69 => Some("Global Flick"),
+        70 => Some("Global Pinch Start"),
+        71 => Some("Global Pinch In"),
+        72 => Some("Global Pinch Out"),
+        73 => Some("Global Pinch End"),
+        74 => Some("Global Rotate Start"),
+        75 => Some("Global Rotating"),
+        76 => Some("Global Rotate End"),
+        _ => None,
+    }
+}
+
+/// Names a key by its virtual key code
+struct KeyName(i32);
+
+impl Display for KeyName {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self.0 {
+            0 => "No Key",
+            1 => "Any Key",
+            8 => "Backspace",
+            9 => "Tab",
+            13 => "Enter",
+            16 => "Shift",
+            17 => "Control",
+            18 => "Alt",
+            19 => "Pause",
+            27 => "Escape",
+            32 => "Space",
+            33 => "Page Up",
+            34 => "Page Down",
+            35 => "End",
+            36 => "Home",
+            37 => "Left",
+            38 => "Up",
+            39 => "Right",
+            40 => "Down",
+            45 => "Insert",
+            46 => "Delete",
+            106 => "Keypad *",
+            107 => "Keypad +",
+            109 => "Keypad -",
+            110 => "Keypad .",
+            111 => "Keypad /",
+            // digits and letters are named by themselves
+            key @ 48..=57 | key @ 65..=90 => return write!(f, "{}", key as u8 as char),
+            key @ 96..=105 => return write!(f, "Keypad {}", key - 96),
+            key @ 112..=123 => return write!(f, "F{}", key - 111),
+            key => return write!(f, "Key {}", key),
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use super::super::super::ID;
+    use super::{EventType, Event};
+    use super::EventType::*;
+
+    #[test]
+    fn every_event_type_is_known() {
+        let id = ID("00000000-0000-0000-0000-000000000000".to_owned());
+        let kinds: Vec<_> = (0..14).map(|eventtype| EventType::new(eventtype, 0, &id)).collect();
+        assert!(kinds.iter().all(Option::is_some));
+        assert_eq!(EventType::new(14, 0, &id), None);
+    }
+
+    #[test]
+    fn file_names_are_read_back() {
+        for event_type in vec![Create(0), Alarm(3), Keyboard(65), Other(10), Draw(64), Gesture(2)] {
+            assert_eq!(EventType::from_file_name(&event_type.file_name()), Some(event_type));
         }
+        assert_eq!(EventType::from_file_name("Alarm.gml"), None);
+        assert_eq!(EventType::from_file_name("Sleep_0.gml"), None);
+    }
+
+    #[test]
+    fn events_are_named_the_way_the_ide_does() {
+        assert_eq!(Step(1).to_string(), "Begin Step");
+        assert_eq!(Alarm(3).to_string(), "Alarm 3");
+        assert_eq!(Other(12).to_string(), "User Event 2");
+        assert_eq!(Draw(64).to_string(), "Draw GUI");
+        assert_eq!(KeyPress(32).to_string(), "Key Press - Space");
+        assert_eq!(Mouse(4).to_string(), "Mouse - Left Pressed");
+    }
+
+    #[test]
+    fn collision_is_with_the_object_collided_with() {
+        let event: Event = serde_json::from_str(r#"{
+            "id": "9b3b1a4e-0f2d-4a0e-8a7e-5d6c2f1b9e01",
+            "modelName": "GMEvent",
+            "mvc": "1.0",
+            "IsDnD": false,
+            "collisionObjectId": "5a1c7e3d-2b4f-4e6a-8c9d-0f1e2d3c4b5a",
+            "enumb": 0,
+            "eventtype": 4,
+            "m_owner": "1f6e4b43-3b9a-4b39-9d4b-2cbdbb0e2e7d"
+        }"#).unwrap();
+        assert_eq!(event.event_type(), Some(Collision(ID("5a1c7e3d-2b4f-4e6a-8c9d-0f1e2d3c4b5a".to_owned()))));
     }
 }
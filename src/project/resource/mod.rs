@@ -1,10 +1,11 @@
 use std::fs::File;
 use std::path::{Path, PathBuf};
 
-use serde_json;
+use serde_json::{self, Value};
 
 use error::Error;
 use super::source::Source;
+use super::{gms23, gmx, json};
 
 mod tag;
 mod object;
@@ -25,17 +26,34 @@ enum YY {
     Script(Script),
 }
 
+/// The contents of a resource's file in the layout of either version of GameMaker Studio 2, or of
+/// GameMaker Studio 1.4
+#[derive(Clone, Debug)]
+enum Layout {
+    GMS2(YY),
+    GMS23(gms23::YY),
+    GMX(gmx::YY),
+}
+
 #[derive(Clone, Debug)]
 pub struct Resource {
     path: PathBuf,
-    resource: YY,
+    resource: Layout,
 }
 
 impl Resource {
-    /// Loads a resource from the `.yy` file
+    /// Loads a resource from the `.yy` file, or the `.object.gmx` or `.gml` file in GameMaker Studio 1.4
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Resource, Error> {
+        if gmx::is_gmx_resource(path.as_ref()) {
+            return Ok(
+                Self {
+                    path: path.as_ref().parent().unwrap().to_owned(),
+                    resource: Layout::GMX(gmx::YY::load(path.as_ref())?),
+                }
+            );
+        }
         let file = File::open(path.as_ref()).map_err(|_| Error::missing_resource(path.as_ref()))?;
-        let resource = serde_json::from_reader(file)?;
+        let resource = Self::layout(file).map_err(|error| error.in_file(path.as_ref()))?;
         Ok(
             Self {
                 path: path.as_ref().parent().unwrap().to_owned(),
@@ -44,13 +62,26 @@ impl Resource {
         )
     }
 
+    /// Reads a `.yy` file, working out which version of GameMaker Studio 2 laid it out
+    fn layout(file: File) -> Result<Layout, Error> {
+        let value: Value = json::from_reader(file)?;
+        if gms23::is_gms23(&value) {
+            Ok(Layout::GMS23(serde_json::from_value(value)?))
+        } else {
+            Ok(Layout::GMS2(serde_json::from_value(value)?))
+        }
+    }
+
     /// Lists all this resource's associated `.gml` and `.gmlpp` files
     pub fn sources(&self) -> Vec<Source> {
-        use self::YY::*;
-        match &self.resource {
-            &Object(ref object) => object.sources().into_iter().map(|source| source.resolved_to(self.path.clone())).collect(),
-            &Script(ref script) => script.sources().into_iter().map(|source| source.resolved_to(self.path.clone())).collect(),
-            _ => vec![],
-        }
+        let sources = match &self.resource {
+            &Layout::GMS2(YY::Object(ref object)) => object.sources(),
+            &Layout::GMS2(YY::Script(ref script)) => script.sources(),
+            &Layout::GMS23(gms23::YY::Object(ref object)) => object.sources(),
+            &Layout::GMS23(gms23::YY::Script(ref script)) => script.sources(),
+            &Layout::GMX(gmx::YY::Object(ref object)) => object.sources(),
+            &Layout::GMX(gmx::YY::Script(ref script)) => script.sources(),
+        };
+        sources.into_iter().map(|source| source.resolved_to(self.path.clone())).collect()
     }
 }
@@ -32,6 +32,74 @@ pub struct Object {
 impl Object {
     /// Returns the source files for this Object. One for each event.
     pub fn sources(&self) -> Vec<Source> {
-        vec![]
+        self.event_list
+            .iter()
+            // events of kinds that are not known about have no file that can be found
+            .filter_map(|event| event.event_type())
+            .map(|event_type| Source::from(event_type.file_name()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use serde_json;
+    use super::Object;
+
+    /// An object with a Create and a Step event, as GameMaker Studio 2 writes it
+    const OBJECT: &str = r#"{
+        "id": "1f6e4b43-3b9a-4b39-9d4b-2cbdbb0e2e7d",
+        "modelName": "GMObject",
+        "mvc": "1.0",
+        "name": "obj_player",
+        "eventList": [
+            {
+                "id": "9b3b1a4e-0f2d-4a0e-8a7e-5d6c2f1b9e01",
+                "modelName": "GMEvent",
+                "mvc": "1.0",
+                "IsDnD": false,
+                "collisionObjectId": "00000000-0000-0000-0000-000000000000",
+                "enumb": 0,
+                "eventtype": 0,
+                "m_owner": "1f6e4b43-3b9a-4b39-9d4b-2cbdbb0e2e7d"
+            },
+            {
+                "id": "c4d2e8f1-7a6b-4c3d-9e2f-1a0b8c7d6e02",
+                "modelName": "GMEvent",
+                "mvc": "1.0",
+                "IsDnD": false,
+                "collisionObjectId": "00000000-0000-0000-0000-000000000000",
+                "enumb": 0,
+                "eventtype": 3,
+                "m_owner": "1f6e4b43-3b9a-4b39-9d4b-2cbdbb0e2e7d"
+            }
+        ],
+        "maskSpriteId": "00000000-0000-0000-0000-000000000000",
+        "parentObjectId": "00000000-0000-0000-0000-000000000000",
+        "persistent": false,
+        "physicsAngularDamping": 0.1,
+        "physicsDensity": 0.5,
+        "physicsFriction": 0.2,
+        "physicsGroup": 0,
+        "physicsKinematic": false,
+        "physicsLinearDamping": 0.1,
+        "physicsObject": false,
+        "physicsRestitution": 0.1,
+        "physicsSensor": false,
+        "physicsShape": 1,
+        "physicsShapePoints": null,
+        "physicsStartAwake": true,
+        "solid": false,
+        "spriteId": "00000000-0000-0000-0000-000000000000",
+        "visible": true
+    }"#;
+
+    #[test]
+    fn object_has_a_source_for_each_event() {
+        let object: Object = serde_json::from_str(OBJECT).unwrap();
+        let files: Vec<_> = object.sources().iter().map(|source| source.gml()).collect();
+        assert_eq!(files, vec![PathBuf::from("Create_0.gml"), PathBuf::from("Step_0.gml")]);
     }
 }
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::Path;
 
 use super::super::ID;
 use super::Resource;
@@ -13,11 +14,32 @@ pub struct ResourceTag {
 }
 
 impl ResourceTag {
+    /// Tags a resource which is kept at a path, relative to the project
+    pub fn new(id: ID, resource_path: String, resource_type: ResourceType) -> Self {
+        ResourceTag {
+            key: id.clone(),
+            value: ResourceValue { id, config_deltas: None, resource_path, resource_type },
+        }
+    }
+
     /// The type of resource that is being tagged
     pub fn resource_type(&self) -> ResourceType {
         self.value.resource_type
     }
 
+    /// The id of the resource that is being tagged
+    pub fn id(&self) -> &ID {
+        &self.key
+    }
+
+    /// The name of the resource that is being tagged, which its file is named after. Files in
+    /// GameMaker Studio 1.4 have two extensions, such as `.object.gmx`
+    pub fn name(&self) -> Option<String> {
+        let path = self.value.resource_path.replace("\\", "/");
+        let file_name = Path::new(&path).file_name()?.to_string_lossy().into_owned();
+        file_name.split('.').next().map(str::to_owned)
+    }
+
     /// Retrieves the resource from the cache or file system
     pub fn resource(&self, base_path: &str, cache: &mut HashMap<ID, Resource>) -> Result<Resource, Error> {
         match cache.get(&self.key).cloned() {
@@ -1,8 +1,16 @@
+use std::fs::File;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
-/// A `.gml` source file and it's corresponding `.gmlpp` and helper files if required
+use error::Error;
+use super::gmx::CodeAction;
+use super::resource::EventType;
+use super::Project;
+
+/// A `.gml` source file and it's corresponding `.gmlpp` and helper files if required. In GameMaker
+/// Studio 1.4 the code of events is kept in the object's file instead, so there is no `.gml` file
 #[derive(Clone, Debug)]
-pub struct Source(PathBuf);
+pub struct Source(PathBuf, Option<CodeAction>);
 
 impl Source {
     /// The path to the `.gml` file for this source
@@ -15,21 +23,72 @@ impl Source {
         self.0.with_extension("gmlpp")
     }
 
+    /// Writes the compiled code, either to the `.gml` file or into the code action it belongs in
+    pub fn write(&self, code: &str) -> Result<(), Error> {
+        match self.1 {
+            Some(ref action) => action.write(code),
+            None => Ok(File::create(self.gml())?.write_all(code.as_bytes())?),
+        }
+    }
+
     /// The path to the helper `.gml` file for this source
     pub fn helper(&self) -> PathBuf { 
         // TODO: this is the wrong path
         self.0.with_extension("gml")
     }
 
+    /// The name of the script this is the source of. Scripts are kept in a folder of the same name,
+    /// or all together in the scripts folder in GameMaker Studio 1.4, while events are named after
+    /// the event within the object's folder
+    pub fn script_name(&self) -> Option<String> {
+        let stem = self.0.file_stem()?;
+        let folder = self.0.parent()?.file_name()?;
+        if folder == stem || (self.1.is_none() && folder == "scripts") {
+            Some(stem.to_string_lossy().into_owned())
+        } else {
+            None
+        }
+    }
+
+    /// The event this is the source of, and the name of the object it belongs to, judging by the
+    /// file's name and the folder it is in
+    pub fn event(&self) -> Option<(String, EventType)> {
+        let event_type = EventType::from_file_name(&self.0.file_name()?.to_string_lossy())?;
+        let object = self.0.parent()?.file_name()?.to_string_lossy().into_owned();
+        Some((object, event_type))
+    }
+
+    /// Describes what this is the source of, such as "script scr_move", "the Step event of
+    /// obj_player" or "the collision of obj_player with obj_wall"
+    pub fn description(&self, project: &Project) -> Option<String> {
+        if let Some(name) = self.script_name() {
+            return Some(format!("script {}", name));
+        }
+        match self.event()? {
+            (object, EventType::Collision(ref id)) => {
+                // the id is all there is to go on if the target is not in the project
+                let target = project.object_name(id).unwrap_or_else(|| id.to_string());
+                Some(format!("the collision of {} with {}", object, target))
+            }
+            (object, event_type) => Some(format!("the {} event of {}", event_type, object)),
+        }
+    }
+
     /// Determines the corresponding source files for a gml or gmlpp file
     pub fn from<P: AsRef<Path>>(path: P) -> Self {
-        Source(path.as_ref().to_owned())
+        Source(path.as_ref().to_owned(), None)
+    }
+
+    /// Puts the compiled code of this source into a code action rather than a `.gml` file
+    pub fn in_action(self, action: CodeAction) -> Self {
+        Source(self.0, Some(action))
     }
 
     /// Adjusts all the paths of this source to the provided base path
     pub fn resolved_to(self, mut path: PathBuf) -> Self {
+        let action = self.1.map(|action| action.resolved_to(path.clone()));
         path.push(self.0);
-        Source(path)
+        Source(path, action)
     }
 }
 
@@ -0,0 +1,52 @@
+//! The functions built into GameMaker Studio. The signatures of the common ones are bundled in
+//! `builtins.json`, while `functions.txt` lists the name of every one, so calls to the rest are
+//! still known to be built in
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json;
+
+use gmlpp::ast::Type;
+use super::Signature;
+
+/// How a built in function is described in `builtins.json`. Parameters with a `null` type accept
+/// anything
+#[derive(Deserialize)]
+struct Builtin {
+    #[serde(default)]
+    parameters: Vec<Option<Type>>,
+    /// How many of the last parameters can be left out. A variadic parameter must be given at least
+    /// one value unless it is optional
+    #[serde(default)]
+    optional: usize,
+    #[serde(default)]
+    variadic: bool,
+    #[serde(default)]
+    returns: Option<Type>,
+}
+
+impl From<Builtin> for Signature {
+    fn from(builtin: Builtin) -> Signature {
+        Signature {
+            required: builtin.parameters.len() - builtin.optional,
+            parameters: builtin.parameters,
+            variadic: builtin.variadic,
+            returns: builtin.returns,
+        }
+    }
+}
+
+/// Loads the names of all the built in functions, including those without a known signature
+pub fn names() -> HashSet<String> {
+    include_str!("functions.txt").lines().map(str::to_owned).collect()
+}
+
+/// Loads the signatures of the built in functions
+pub fn load() -> HashMap<String, Signature> {
+    let builtins: HashMap<String, Builtin> = serde_json::from_str(include_str!("builtins.json"))
+        .expect("builtins.json is not a valid list of functions");
+    builtins
+        .into_iter()
+        .map(|(name, builtin)| (name, builtin.into()))
+        .collect()
+}
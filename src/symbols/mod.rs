@@ -0,0 +1,78 @@
+use std::collections::{HashMap, HashSet};
+
+use gmlpp::AST;
+use gmlpp::ast::StatementKind;
+
+mod signature;
+mod builtins;
+
+pub use self::signature::Signature;
+
+/// Declarations which are visible from every file in a project
+#[derive(Clone, Debug)]
+pub struct Symbols {
+    enums: HashMap<String, Vec<String>>,
+    /// Every script in the project. Those written in plain GML have no known signature
+    scripts: HashMap<String, Option<Signature>>,
+    builtins: HashMap<String, Signature>,
+    /// The name of every built in function, many of which have no known signature
+    functions: HashSet<String>,
+}
+
+impl Symbols {
+    /// Creates a table which knows only the built in functions
+    pub fn new() -> Self {
+        Symbols { enums: HashMap::new(), scripts: HashMap::new(), builtins: builtins::load(), functions: builtins::names() }
+    }
+
+    /// Records that there is a script with this name, without knowing what it accepts
+    pub fn declare_script(&mut self, name: String) {
+        self.scripts.entry(name).or_insert(None);
+    }
+
+    /// Records all the top level declarations in a file, replacing any previous declarations of the
+    /// same name. Scripts are also recorded by their name, so calls to them can be checked
+    pub fn collect(&mut self, script: Option<String>, ast: &AST) {
+        if let Some(name) = script {
+            self.scripts.insert(name, Some(Signature::of(ast.code())));
+        }
+        for statement in &ast.code().body.0 {
+            if let StatementKind::EnumDecl(ref name, ref members) = statement.kind {
+                let members = members.iter().map(|member| member.name.0.clone()).collect();
+                self.enums.insert(name.0.clone(), members);
+            }
+        }
+    }
+
+    /// The names of the members of an enum, if there is an enum with this name
+    pub fn enum_members(&self, name: &str) -> Option<&Vec<String>> {
+        self.enums.get(name)
+    }
+
+    /// The signature of a script or built in function, if it is known
+    pub fn signature(&self, name: &str) -> Option<&Signature> {
+        match self.scripts.get(name) {
+            Some(signature) => signature.as_ref(),
+            None => self.builtins.get(name),
+        }
+    }
+
+    /// Whether there is a script or built in function with this name
+    pub fn is_function(&self, name: &str) -> bool {
+        self.scripts.contains_key(name) || self.builtins.contains_key(name) || self.functions.contains(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gmlpp::AST;
+    use super::Symbols;
+
+    #[test]
+    fn enums_are_collected_with_their_members() {
+        let mut symbols = Symbols::new();
+        symbols.collect(None, &AST::from_reader("enum Dir { Up, Down = 5, Left }\n".as_bytes()).unwrap());
+        assert_eq!(symbols.enum_members("Dir"), Some(&vec!["Up".to_owned(), "Down".to_owned(), "Left".to_owned()]));
+        assert_eq!(symbols.enum_members("Colour"), None);
+    }
+}
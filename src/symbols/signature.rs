@@ -0,0 +1,60 @@
+use gmlpp::ast::{ArgumentList, Code, Type};
+
+/// The arguments a script accepts and what it returns
+#[derive(Clone, Debug, Default)]
+pub struct Signature {
+    /// The declared type of each parameter, in order
+    pub parameters: Vec<Option<Type>>,
+    /// How many arguments must be passed, the rest being optional
+    pub required: usize,
+    /// Whether the last parameter collects any number of extra arguments
+    pub variadic: bool,
+    pub returns: Option<Type>,
+}
+
+impl Signature {
+    /// Reads the signature from the argument list and return type at the top of a script
+    pub fn of(code: &Code) -> Self {
+        let mut signature = Signature { returns: code.returns, ..Signature::default() };
+        let mut args = &code.args;
+        loop {
+            args = match *args {
+                ArgumentList::Argument(_, ty, ref rest) => {
+                    signature.parameters.push(ty);
+                    signature.required = signature.parameters.len();
+                    rest
+                }
+                ArgumentList::DefaultArgument(_, ty, _, ref rest)
+                | ArgumentList::OptionalArgument(_, ty, ref rest) => {
+                    signature.parameters.push(ty);
+                    rest
+                }
+                ArgumentList::VariadicArgument(_, ty) => {
+                    signature.parameters.push(ty);
+                    signature.variadic = true;
+                    break;
+                }
+                ArgumentList::End => break,
+            };
+        }
+        signature
+    }
+
+    /// The most arguments which can be passed, if there is a limit
+    pub fn allowed(&self) -> Option<usize> {
+        if self.variadic {
+            None
+        } else {
+            Some(self.parameters.len())
+        }
+    }
+
+    /// The type expected of the argument in this position, if it is known
+    pub fn parameter(&self, index: usize) -> Option<Type> {
+        match self.parameters.get(index) {
+            Some(ty) => *ty,
+            None if self.variadic => self.parameters.last().and_then(|ty| *ty),
+            None => None,
+        }
+    }
+}